@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde_yaml::Value;
+use yaml_front_matter::{Collection, Editor, FrontMatterError, Parser};
+
+/// An edit of the top-level keys of a front matter
+pub enum Edit {
+    Set(String, Value),
+    Unset(String),
+    Rename(String, String),
+}
+
+/// The documents to edit and how to edit them
+#[derive(clap::Args)]
+pub struct EditOptions {
+    /// Prints the changes as a diff instead of writing them
+    #[clap(long)]
+    dry_run: bool,
+    /// The glob pattern selecting the documents under directories
+    #[clap(long, default_value = "**/*.md")]
+    glob: String,
+    /// The Markdown documents to edit, or directories of documents
+    #[clap(required = true)]
+    paths: Vec<PathBuf>,
+}
+
+impl Edit {
+    /// Parses a `key=value` assignment, where the value is YAML.
+    pub fn set(assignment: &str) -> Result<Edit, Box<dyn Error>> {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key=value`, found `{}`", assignment))?;
+
+        Ok(Edit::Set(key.to_string(), serde_yaml::from_str(value)?))
+    }
+
+    /// Applies the edit to the `markdown` document, `None` if the document
+    /// is left unchanged.
+    ///
+    /// The front matter must open the document, so the thematic breaks of
+    /// a document without front matter are never taken for its fences.
+    pub fn apply(&self, markdown: &str) -> Result<Option<String>, FrontMatterError> {
+        let parser = Parser::new().strict(true).allow_leading_blank_lines(true);
+        let mut editor = Editor::with_parser(markdown, &parser)?;
+
+        match self {
+            Edit::Set(key, value) => editor.set(key, value)?,
+            Edit::Unset(key) => {
+                editor.remove(key);
+            }
+            Edit::Rename(from, to) => {
+                editor.rename(from, to)?;
+            }
+        }
+
+        Ok(Some(editor.into_string()).filter(|edited| edited != markdown))
+    }
+}
+
+impl EditOptions {
+    /// Applies `edit` to every document, writing the path of every edited
+    /// document, or the diff of the edit in a dry run, to `out`.
+    ///
+    /// Returns `false` if any document couldn't be edited.
+    pub fn apply<W: Write>(&self, edit: &Edit, out: &mut W) -> Result<bool, Box<dyn Error>> {
+        let mut edited = true;
+
+        for path in self.documents()? {
+            let result = match path {
+                Ok(path) => self
+                    .edit(edit, &path, out)
+                    .map_err(|err| format!("{}: {}", path.display(), err)),
+                Err(err) => Err(err.to_string()),
+            };
+
+            if let Err(err) = result {
+                eprintln!("yfm: {}", err);
+                edited = false;
+            }
+        }
+
+        Ok(edited)
+    }
+
+    /// Applies `edit` to the document at `path`.
+    fn edit<W: Write>(&self, edit: &Edit, path: &Path, out: &mut W) -> Result<(), Box<dyn Error>> {
+        let markdown = fs::read_to_string(path)?;
+
+        match edit.apply(&markdown)? {
+            Some(edited) if self.dry_run => write!(out, "{}", diff(path, &markdown, &edited))?,
+            Some(edited) => {
+                replace(path, &edited)?;
+                writeln!(out, "{}", path.display())?;
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Lists the documents to edit, walking directories.
+    fn documents(&self) -> Result<Vec<Result<PathBuf, FrontMatterError>>, FrontMatterError> {
+        let mut documents = Vec::new();
+
+        for path in &self.paths {
+            match path.is_dir() {
+                true => documents.extend(Collection::new(path, &self.glob)?.paths()),
+                false => documents.push(Ok(path.clone())),
+            }
+        }
+
+        Ok(documents)
+    }
+}
+
+/// Renders the lines changed from `before` to `after` as a unified diff
+/// with a single hunk, as edits are all within the front matter.
+pub fn diff(path: &Path, before: &str, after: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let before = before.split_inclusive('\n').collect::<Vec<_>>();
+    let after = after.split_inclusive('\n').collect::<Vec<_>>();
+    let prefix = before
+        .iter()
+        .zip(&after)
+        .take_while(|(before, after)| before == after)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(before, after)| before == after)
+        .count();
+    let start = prefix.saturating_sub(CONTEXT);
+    let end = (before.len() - suffix + CONTEXT).min(before.len());
+    let trailing = end - (before.len() - suffix);
+    let added = &after[prefix..after.len() - suffix];
+    let mut diff = format!(
+        "--- a/{path}\n+++ b/{path}\n@@ -{},{} +{},{} @@\n",
+        start + 1,
+        end - start,
+        start + 1,
+        prefix - start + added.len() + trailing,
+        path = path.display()
+    );
+    let lines = before[start..prefix]
+        .iter()
+        .map(|line| (' ', line))
+        .chain(
+            before[prefix..before.len() - suffix]
+                .iter()
+                .map(|line| ('-', line)),
+        )
+        .chain(added.iter().map(|line| ('+', line)))
+        .chain(
+            before[before.len() - suffix..end]
+                .iter()
+                .map(|line| (' ', line)),
+        );
+
+    for (marker, line) in lines {
+        diff.push(marker);
+        diff.push_str(line.trim_end_matches(&['\r', '\n'][..]));
+        diff.push('\n');
+    }
+
+    diff
+}
+
+/// Replaces the contents of the file at `path` atomically, by writing them
+/// to a file in the same directory which is then renamed over `path`.
+pub fn replace(path: &Path, contents: &str) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(".{}.yfm", name));
+    let result = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.set_permissions(fs::metadata(path)?.permissions())?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{diff, replace, Edit};
+
+    const MARKDOWN: &str = "---\ntitle: Hello\ncategories: [rust]\n---\n# Hello\n";
+
+    #[test]
+    fn applies_edits() {
+        let set = Edit::set("draft=false").unwrap().apply(MARKDOWN).unwrap();
+        let rename = Edit::Rename("categories".to_string(), "tags".to_string())
+            .apply(MARKDOWN)
+            .unwrap();
+        let unset = Edit::Unset("draft".to_string()).apply(MARKDOWN).unwrap();
+
+        assert_eq!(
+            set.unwrap(),
+            "---\ntitle: Hello\ncategories: [rust]\ndraft: false\n---\n# Hello\n"
+        );
+        assert_eq!(
+            rename.unwrap(),
+            "---\ntitle: Hello\ntags: [rust]\n---\n# Hello\n"
+        );
+        assert!(unset.is_none());
+        assert!(Edit::set("draft").is_err());
+    }
+
+    #[test]
+    fn fails_to_rename_onto_existing_key() {
+        let markdown = "---\ncategories: [x]\ntags: [y]\n---\n";
+        let rename = Edit::Rename("categories".to_string(), "tags".to_string());
+
+        assert_eq!(
+            rename.apply(markdown).unwrap_err().to_string(),
+            "front matter already has a `tags` key at line 3, column 1"
+        );
+    }
+
+    #[test]
+    fn adds_front_matter_above_thematic_breaks() {
+        let markdown = "# Hello\n\nIntro\n\n---\n\nMiddle\n\n---\n\nEnd\n";
+        let single = "# Hello\n\n---\n\nEnd\n";
+        let set = Edit::set("draft=false").unwrap();
+
+        assert_eq!(
+            set.apply(markdown).unwrap().unwrap(),
+            format!("---\ndraft: false\n---\n{}", markdown)
+        );
+        assert_eq!(
+            set.apply(single).unwrap().unwrap(),
+            format!("---\ndraft: false\n---\n{}", single)
+        );
+    }
+
+    #[test]
+    fn renders_diff_of_edit() {
+        let edited = MARKDOWN.replace("categories:", "tags:");
+
+        assert_eq!(
+            diff(Path::new("post.md"), MARKDOWN, &edited),
+            "--- a/post.md\n+++ b/post.md\n@@ -1,5 +1,5 @@\n ---\n title: Hello\n-categories: [rust]\n+tags: [rust]\n ---\n # Hello\n"
+        );
+    }
+
+    #[test]
+    fn replaces_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("post.md");
+
+        fs::write(&path, MARKDOWN).unwrap();
+        replace(&path, "# Replaced\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Replaced\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use yaml_front_matter::{
+    Collection, DuplicateKeyPolicy, FrontMatterError, Location, Metadata, Parser, Schema,
+};
+
+/// A front matter problem of a Markdown document
+#[derive(Debug, Serialize)]
+pub struct Problem {
+    file: PathBuf,
+    line: Option<usize>,
+    column: Option<usize>,
+    code: &'static str,
+    message: String,
+}
+
+impl Problem {
+    fn new(file: &Path, code: &'static str, message: String, location: Option<&Location>) -> Self {
+        Problem {
+            file: file.to_path_buf(),
+            line: location.map(Location::line),
+            column: location.map(Location::column),
+            code,
+            message,
+        }
+    }
+
+    /// Describes `err`, leaving out its location as it is reported apart.
+    fn from_error(file: &Path, err: &FrontMatterError) -> Self {
+        let code = match err {
+            FrontMatterError::MissingOpeningFence => "missing-front-matter",
+            FrontMatterError::MissingClosingFence { .. } => "unterminated-front-matter",
+            FrontMatterError::Syntax { .. } => "syntax",
+            FrontMatterError::Deserialize { .. } => "invalid-front-matter",
+            FrontMatterError::DuplicateKey(_) => "duplicate-key",
+            FrontMatterError::Io(_) => "io",
+            _ => "error",
+        };
+        let message = err.to_string();
+        let message = match err.location() {
+            Some(location) => message
+                .strip_suffix(&format!(" at {}", location))
+                .unwrap_or(&message)
+                .to_string(),
+            None => message,
+        };
+        let message = match crate::cause(err) {
+            Some(cause) => format!("{}: {}", message, cause),
+            None => message,
+        };
+
+        Problem::new(file, code, message, err.location())
+    }
+}
+
+/// The format to report problems in
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum Report {
+    Text,
+    Json,
+}
+
+/// Lints the Markdown documents under `root` matching `glob`, or `root`
+/// itself if it is a file.
+pub fn lint(
+    root: &Path,
+    glob: &str,
+    schema: Option<&Path>,
+) -> Result<Vec<Problem>, Box<dyn Error>> {
+    let schema = match schema {
+        Some(schema) => {
+            let schema = serde_json::from_str(&fs::read_to_string(schema)?)?;
+
+            Some(Schema::new(&schema)?)
+        }
+        None => None,
+    };
+    let paths = match root.is_file() {
+        true => vec![Ok(root.to_path_buf())],
+        false => Collection::new(root, glob)?.paths(),
+    };
+    // Thematic breaks in the body don't open front matter
+    let parser = Parser::new().strict(true).allow_leading_blank_lines(true);
+    let mut problems = Vec::new();
+
+    for path in paths {
+        let path = match path {
+            Ok(path) => path,
+            Err(FrontMatterError::File { path, source }) => {
+                problems.push(Problem::from_error(&path, &source));
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(markdown) => {
+                problems.extend(lint_document(&parser, schema.as_ref(), &path, &markdown))
+            }
+            Err(err) => problems.push(Problem::from_error(&path, &err.into())),
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Lints the `markdown` document read from `file`.
+pub fn lint_document(
+    parser: &Parser,
+    schema: Option<&Schema>,
+    file: &Path,
+    markdown: &str,
+) -> Vec<Problem> {
+    match parser.split(markdown) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return vec![Problem::from_error(
+                file,
+                &FrontMatterError::MissingOpeningFence,
+            )]
+        }
+        Err(err) => return vec![Problem::from_error(file, &err)],
+    }
+
+    let mut problems = parser
+        .duplicate_keys(markdown)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|duplicate| {
+            let message = format!(
+                "duplicate key `{}`, first defined at {}",
+                duplicate.path(),
+                duplicate.first()
+            );
+
+            Problem::new(file, "duplicate-key", message, Some(duplicate.second()))
+        })
+        .collect::<Vec<_>>();
+
+    // Duplicate keys are reported above, keep checking the rest of the front
+    // matter
+    let parser = parser
+        .clone()
+        .duplicate_key_policy(DuplicateKeyPolicy::KeepLast);
+
+    match parser.parse::<Metadata>(markdown) {
+        // Anchored duplicates are not dropped, and are already reported
+        Err(FrontMatterError::DuplicateKey(_)) => {}
+        Err(err) => problems.push(Problem::from_error(file, &err)),
+        Ok(_) => match schema.map(|schema| parser.validate(markdown, schema)) {
+            Some(Err(FrontMatterError::Validation(violations))) => {
+                problems.extend(violations.iter().map(|violation| {
+                    let message = match violation.path() {
+                        "" => violation.message().to_string(),
+                        path => format!("{}: {}", path, violation.message()),
+                    };
+
+                    Problem::new(file, "schema", message, violation.location())
+                }))
+            }
+            Some(Err(err)) => problems.push(Problem::from_error(file, &err)),
+            Some(Ok(())) | None => {}
+        },
+    }
+
+    problems
+}
+
+/// Writes the `problems` to `out` in the `format`.
+pub fn report<W: Write>(
+    out: &mut W,
+    problems: &[Problem],
+    format: Report,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Report::Text => {
+            for problem in problems {
+                write!(out, "{}", problem.file.display())?;
+
+                if let (Some(line), Some(column)) = (problem.line, problem.column) {
+                    write!(out, ":{}:{}", line, column)?;
+                }
+
+                writeln!(out, ": {}: {}", problem.code, problem.message)?;
+            }
+        }
+        Report::Json => writeln!(out, "{}", serde_json::to_string_pretty(problems)?)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use serde_json::json;
+    use yaml_front_matter::{Parser, Schema};
+
+    use super::{lint_document, report, Report};
+
+    fn lint(markdown: &str) -> String {
+        let schema = Schema::new(&json!({
+            "properties": { "tags": { "type": "array", "maxItems": 1 } }
+        }))
+        .unwrap();
+        let problems = lint_document(
+            &Parser::new().strict(true).allow_leading_blank_lines(true),
+            Some(&schema),
+            Path::new("post.md"),
+            markdown,
+        );
+        let mut out = Vec::new();
+
+        report(&mut out, &problems, Report::Text).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn reports_missing_and_unterminated_front_matter() {
+        assert_eq!(
+            lint("# Post\n"),
+            "post.md: missing-front-matter: missing opening front matter fence\n"
+        );
+        assert_eq!(
+            lint("---\ntitle: Post\n"),
+            "post.md:1:1: unterminated-front-matter: missing closing front matter fence for the fence opened at line 1\n"
+        );
+    }
+
+    #[test]
+    fn ignores_thematic_breaks_without_front_matter() {
+        let missing = "post.md: missing-front-matter: missing opening front matter fence\n";
+
+        assert_eq!(lint("# T\n\n---\n\nx\n\n---\n"), missing);
+        assert_eq!(lint("# T\n\n---\n\nx\n"), missing);
+        assert_eq!(lint("\n---\ntitle: Post\n---\n"), "");
+    }
+
+    #[test]
+    fn reports_syntax_errors_and_duplicate_keys() {
+        assert_eq!(
+            lint("---\ntitle: [\n---\n"),
+            "post.md:3:1: syntax: front matter is not valid YAML: did not find expected node content, while parsing a flow node\n"
+        );
+        assert_eq!(
+            lint("---\ntitle: a\ntitle: b\n---\n"),
+            "post.md:3:1: duplicate-key: duplicate key `title`, first defined at line 2, column 1\n"
+        );
+        assert_eq!(
+            lint("---\na: &x 1\na: 2\nb: *x\n---\n"),
+            "post.md:3:1: duplicate-key: duplicate key `a`, first defined at line 2, column 1\n"
+        );
+    }
+
+    #[test]
+    fn reports_schema_violations() {
+        assert_eq!(lint("---\ntags: [a]\n---\n"), "");
+        assert_eq!(
+            lint("---\ntags: [a, b]\n---\n"),
+            "post.md:2:7: schema: /tags: [\"a\",\"b\"] has more than 1 item\n"
+        );
+    }
+}
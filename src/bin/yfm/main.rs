@@ -0,0 +1,351 @@
+//! `yfm` inspects and queries the front matter of Markdown documents,
+//! following the parsing rules of the `yaml-front-matter` crate.
+//!
+//! ```ignore
+//! yfm show --format json post.md
+//! yfm body post.md
+//! yfm get author.name post.md
+//! yfm lint --schema schema.json content
+//! yfm set --dry-run draft=false content/posts
+//! yfm rename categories tags content/posts
+//! ```
+mod edit;
+mod lint;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::Parser as _;
+use serde::Serialize;
+use serde_yaml::Value;
+use yaml_front_matter::{FrontMatterError, Metadata, Parser};
+
+use crate::edit::{Edit, EditOptions};
+use crate::lint::Report;
+
+/// Inspects and queries the front matter of Markdown documents
+#[derive(clap::Parser)]
+#[clap(name = "yfm", version)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Prints the front matter of a document
+    Show {
+        /// The format to print the front matter in
+        #[clap(long, value_enum, default_value = "yaml")]
+        format: Output,
+        /// The Markdown document, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+    /// Prints the body of a document, without its front matter
+    Body {
+        /// The Markdown document, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+    /// Prints the front matter value at a dotted key path, such as
+    /// `author.name` or `tags.0`
+    Get {
+        /// The dotted key path of the value
+        path: String,
+        /// The format to print lists and mappings in, other values are
+        /// printed as is
+        #[clap(long, value_enum, default_value = "yaml")]
+        format: Output,
+        /// The Markdown document, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+    /// Reports the front matter problems of the Markdown documents under a
+    /// directory, exiting with a non-zero code if any
+    Lint {
+        /// The format to report problems in
+        #[clap(long, value_enum, default_value = "text")]
+        format: Report,
+        /// The glob pattern selecting the documents under the directory
+        #[clap(long, default_value = "**/*.md")]
+        glob: String,
+        /// A JSON Schema file every front matter must match
+        #[clap(long)]
+        schema: Option<PathBuf>,
+        /// The directory to lint, or a single Markdown document
+        path: PathBuf,
+    },
+    /// Sets a top-level front matter key of documents, leaving the rest of
+    /// the documents untouched
+    Set {
+        /// The key and its YAML value, such as `draft=false`
+        assignment: String,
+        #[clap(flatten)]
+        options: EditOptions,
+    },
+    /// Removes a top-level front matter key of documents
+    Unset {
+        /// The key to remove
+        key: String,
+        #[clap(flatten)]
+        options: EditOptions,
+    },
+    /// Renames a top-level front matter key of documents, keeping its value
+    Rename {
+        /// The key to rename
+        from: String,
+        /// The new name of the key
+        to: String,
+        #[clap(flatten)]
+        options: EditOptions,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Output {
+    Yaml,
+    Json,
+}
+
+impl Command {
+    fn file(&self) -> Option<&Path> {
+        let file = match self {
+            Command::Show { file, .. } | Command::Body { file } | Command::Get { file, .. } => {
+                file.as_deref()
+            }
+            _ => None,
+        };
+
+        file.filter(|file| *file != Path::new("-"))
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let result = match &cli.command {
+        Command::Lint {
+            format,
+            glob,
+            schema,
+            path,
+        } => lint::lint(path, glob, schema.as_deref()).and_then(|problems| {
+            lint::report(&mut out, &problems, *format)?;
+            Ok(problems.is_empty())
+        }),
+        Command::Set {
+            assignment,
+            options,
+        } => Edit::set(assignment).and_then(|edit| options.apply(&edit, &mut out)),
+        Command::Unset { key, options } => options.apply(&Edit::Unset(key.clone()), &mut out),
+        Command::Rename { from, to, options } => {
+            options.apply(&Edit::Rename(from.clone(), to.clone()), &mut out)
+        }
+        command => {
+            let file = command.file();
+
+            if let Err(err) = read(file).and_then(|markdown| run(command, &markdown, &mut out)) {
+                let message = match cause(err.as_ref()) {
+                    Some(cause) => format!("{}: {}", err, cause),
+                    None => err.to_string(),
+                };
+
+                match file {
+                    Some(file) => eprintln!("yfm: {}: {}", file.display(), message),
+                    None => eprintln!("yfm: {}", message),
+                }
+
+                process::exit(1);
+            }
+
+            Ok(true)
+        }
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("yfm: {}", err);
+            process::exit(2);
+        }
+    }
+}
+
+/// Reads the Markdown document at `file`, or from stdin if `None`.
+fn read(file: Option<&Path>) -> Result<String, Box<dyn Error>> {
+    let mut markdown = String::new();
+
+    match file {
+        Some(file) => markdown = fs::read_to_string(file)?,
+        None => {
+            io::stdin().read_to_string(&mut markdown)?;
+        }
+    }
+
+    Ok(markdown)
+}
+
+/// Runs `command` on the `markdown` document, writing its output to `out`.
+fn run<W: Write>(command: &Command, markdown: &str, out: &mut W) -> Result<(), Box<dyn Error>> {
+    let parser = Parser::new().strip_fence_newline(true);
+
+    match command {
+        Command::Show { format, .. } => {
+            let metadata = parse(&parser, markdown)?;
+
+            write_value(out, metadata.as_value(), *format)
+        }
+        Command::Body { .. } => {
+            let body = parser.split(markdown)?.map_or(markdown, |split| split.body);
+
+            out.write_all(body.as_bytes())?;
+            Ok(())
+        }
+        Command::Lint { .. }
+        | Command::Set { .. }
+        | Command::Unset { .. }
+        | Command::Rename { .. } => unreachable!("runs on many documents"),
+        Command::Get { path, format, .. } => {
+            let metadata = parse(&parser, markdown)?;
+            let value = metadata
+                .get(path)
+                .ok_or_else(|| format!("no front matter value at `{}`", path))?;
+
+            match value {
+                Value::String(value) => writeln!(out, "{}", value)?,
+                Value::Bool(_) | Value::Number(_) | Value::Null => {
+                    write_value(out, value, Output::Yaml)?
+                }
+                _ => write_value(out, value, *format)?,
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn parse(parser: &Parser, markdown: &str) -> Result<Metadata, FrontMatterError> {
+    parser
+        .parse::<Metadata>(markdown)?
+        .metadata
+        .ok_or(FrontMatterError::MissingOpeningFence)
+}
+
+/// Describes the error causing `err`, if any, leaving out the position the
+/// YAML, TOML and JSON libraries report relative to the front matter rather
+/// than to the document.
+fn cause(err: &(dyn Error + 'static)) -> Option<String> {
+    match err
+        .downcast_ref::<FrontMatterError>()
+        .and_then(FrontMatterError::message)
+    {
+        Some(message) => Some(message.to_string()),
+        None => err.source().map(ToString::to_string),
+    }
+}
+
+/// Writes `value` in the `format` with a trailing line ending.
+fn write_value<W: Write, T: Serialize>(
+    out: &mut W,
+    value: &T,
+    format: Output,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Output::Yaml => write!(out, "{}", serde_yaml::to_string(value)?)?,
+        Output::Json => writeln!(out, "{}", serde_json::to_string_pretty(value)?)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cause, run, Command, Output};
+
+    const MARKDOWN: &str = "---\ntitle: Hello\nauthor:\n  name: Esteban\ntags: [rust, yaml]\ndraft: false\n---\n# Hello\n";
+
+    fn output(command: Command, markdown: &str) -> String {
+        let mut out = Vec::new();
+
+        run(&command, markdown, &mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn shows_front_matter() {
+        let yaml = output(
+            Command::Show {
+                format: Output::Yaml,
+                file: None,
+            },
+            MARKDOWN,
+        );
+        let json = output(
+            Command::Show {
+                format: Output::Json,
+                file: None,
+            },
+            "---\ntitle: Hello\n---\n",
+        );
+
+        assert!(yaml.starts_with("title: Hello\nauthor:\n  name: Esteban\n"));
+        assert_eq!(json, "{\n  \"title\": \"Hello\"\n}\n");
+    }
+
+    #[test]
+    fn prints_body() {
+        assert_eq!(output(Command::Body { file: None }, MARKDOWN), "# Hello\n");
+        assert_eq!(
+            output(Command::Body { file: None }, "# Plain\n"),
+            "# Plain\n"
+        );
+    }
+
+    #[test]
+    fn gets_value_at_path() {
+        let get = |path: &str| {
+            output(
+                Command::Get {
+                    path: path.to_string(),
+                    format: Output::Yaml,
+                    file: None,
+                },
+                MARKDOWN,
+            )
+        };
+
+        assert_eq!(get("author.name"), "Esteban\n");
+        assert_eq!(get("draft"), "false\n");
+        assert_eq!(get("tags"), "- rust\n- yaml\n");
+    }
+
+    #[test]
+    fn fails_on_missing_value() {
+        let command = Command::Get {
+            path: "author.email".to_string(),
+            format: Output::Yaml,
+            file: None,
+        };
+
+        assert!(run(&command, MARKDOWN, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn describes_cause_without_its_position() {
+        let command = Command::Show {
+            format: Output::Yaml,
+            file: None,
+        };
+        let err = run(&command, "---\ntitle: [\n---\n", &mut Vec::new()).unwrap_err();
+
+        assert_eq!(
+            cause(err.as_ref()).unwrap(),
+            "did not find expected node content, while parsing a flow node"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobMatcher};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+use walkdir::WalkDir;
+
+use crate::parser::read_file;
+use crate::{Document, FrontMatterError, Parser};
+
+/// The Markdown documents under a root directory whose path matches a glob
+/// pattern, such as the content of a static site.
+///
+/// Parsing a collection doesn't stop at the first invalid document, every
+/// error is collected along with the path of its document instead. With the
+/// `parallel` feature documents are parsed in parallel.
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use yaml_front_matter::Collection;
+///
+/// #[derive(Deserialize)]
+/// struct Metadata {
+///     title: String,
+/// }
+///
+/// let collection = Collection::new("content", "posts/**/*.md").unwrap();
+/// let posts = collection.parse::<Metadata>();
+///
+/// for (path, document) in posts.documents {
+///     println!("{}: {}", path.display(), document.metadata.title);
+/// }
+///
+/// for err in posts.errors {
+///     eprintln!("{}", err);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Collection {
+    root: PathBuf,
+    matcher: GlobMatcher,
+    parser: Parser,
+}
+
+/// The outcome of parsing a `Collection`.
+pub struct Collected<T> {
+    /// The documents parsed along with their path, in path order
+    pub documents: Vec<(PathBuf, Document<T>)>,
+    /// The errors of the documents which couldn't be read or parsed, each
+    /// naming the file of the document
+    pub errors: Vec<FrontMatterError>,
+}
+
+impl Collection {
+    /// Creates a `Collection` of the files under `root` whose path relative
+    /// to `root` matches the glob `pattern`.
+    pub fn new<P: AsRef<Path>>(root: P, pattern: &str) -> Result<Collection, FrontMatterError> {
+        let glob = Glob::new(pattern).map_err(|err| FrontMatterError::Pattern {
+            pattern: pattern.to_string(),
+            source: Box::new(err),
+        })?;
+
+        Ok(Collection {
+            root: root.as_ref().to_path_buf(),
+            matcher: glob.compile_matcher(),
+            parser: Parser::default(),
+        })
+    }
+
+    /// Sets the `Parser` used to parse every document of the collection.
+    ///
+    /// Documents without front matter are reported as errors.
+    pub fn parser(mut self, parser: Parser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Parses the front matter of every document of the collection into
+    /// `T`.
+    pub fn parse<T: DeserializeOwned + Send>(&self) -> Collected<T> {
+        let mut paths = Vec::new();
+        let mut errors = Vec::new();
+
+        for path in self.paths() {
+            match path {
+                Ok(path) => paths.push(path),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        #[cfg(feature = "parallel")]
+        let parsed = paths
+            .into_par_iter()
+            .map(|path| self.parse_document::<T>(path))
+            .collect::<Vec<_>>();
+        #[cfg(not(feature = "parallel"))]
+        let parsed = paths
+            .into_iter()
+            .map(|path| self.parse_document::<T>(path))
+            .collect::<Vec<_>>();
+
+        let mut documents = Vec::with_capacity(parsed.len());
+
+        for result in parsed {
+            match result {
+                Ok(document) => documents.push(document),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        Collected { documents, errors }
+    }
+
+    /// Lists the paths of the documents of the collection in path order,
+    /// along with the errors found while walking the root directory.
+    pub fn paths(&self) -> Vec<Result<PathBuf, FrontMatterError>> {
+        WalkDir::new(&self.root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        let path = err.path().unwrap_or(&self.root).to_path_buf();
+
+                        return Some(Err(
+                            FrontMatterError::from(io::Error::from(err)).in_file(path)
+                        ));
+                    }
+                };
+                let relative = entry
+                    .path()
+                    .strip_prefix(&self.root)
+                    .unwrap_or(entry.path());
+
+                match entry.file_type().is_file() && self.matcher.is_match(relative) {
+                    true => Some(Ok(entry.into_path())),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    fn parse_document<T: DeserializeOwned>(
+        &self,
+        path: PathBuf,
+    ) -> Result<(PathBuf, Document<T>), FrontMatterError> {
+        let document = read_file(&path, |markdown| {
+            let document = self.parser.parse::<T>(markdown)?;
+
+            Ok(Document {
+                metadata: document
+                    .metadata
+                    .ok_or(FrontMatterError::MissingOpeningFence)?,
+                content: document.content,
+                format: document.format,
+                layout: document.layout,
+            })
+        })?;
+
+        Ok((path, document))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use serde::Deserialize;
+
+    use super::Collection;
+    use crate::FrontMatterError;
+
+    #[derive(Debug, Deserialize)]
+    struct Metadata {
+        title: String,
+    }
+
+    #[test]
+    fn parses_matching_documents_and_collects_errors() {
+        let root = tempfile::tempdir().unwrap();
+        let posts = root.path().join("posts");
+
+        fs::create_dir_all(posts.join("2021")).unwrap();
+        fs::write(posts.join("2021/hello.md"), "---\ntitle: 'Hello'\n---\n").unwrap();
+        fs::write(posts.join("world.md"), "---\ntitle: 'World'\n---\n").unwrap();
+        fs::write(posts.join("broken.md"), "---\ntitle: [\n---\n").unwrap();
+        fs::write(posts.join("notes.txt"), "---\ntitle: 'Notes'\n---\n").unwrap();
+        fs::write(root.path().join("about.md"), "---\ntitle: 'About'\n---\n").unwrap();
+
+        let collected = Collection::new(root.path(), "posts/**/*.md")
+            .unwrap()
+            .parse::<Metadata>();
+        let titles = collected
+            .documents
+            .iter()
+            .map(|(_, document)| document.metadata.title.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(titles, vec!["Hello", "World"]);
+        assert_eq!(collected.errors.len(), 1);
+        assert!(matches!(
+            &collected.errors[0],
+            FrontMatterError::File { path, .. } if path.ends_with("posts/broken.md")
+        ));
+    }
+
+    #[test]
+    fn fails_on_invalid_pattern() {
+        assert!(matches!(
+            Collection::new(".", "posts/[.md"),
+            Err(FrontMatterError::Pattern { .. })
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{Format, FrontMatterError, Layout};
+
+/// A `Document` represents the Markdown file provided as input to
+/// `YamlFrontMatter::parse` associated function.
+///
+/// The document holds four relevant fields:
+///
+/// - `metadata`: A generic type with the structure of the Markdown's
+///   front matter header.
+///
+/// - `content`: The body of the Markdown following the closing fence, byte
+///   for byte, starting with the line ending of the closing fence
+///
+/// - `format`: The format of the front matter header, if any
+///
+/// - `layout`: Where the front matter and the body live in the Markdown,
+///   if the document has front matter
+pub struct Document<T> {
+    /// A generic type with the structure of the Markdown's
+    /// front matter header.
+    pub metadata: T,
+    /// The body of the Markdown following the closing fence, byte for byte
+    pub content: String,
+    /// The format of the front matter header, `None` if the document has
+    /// no front matter
+    pub format: Option<Format>,
+    /// Where the front matter and the body live in the Markdown, `None` if
+    /// the document has no front matter
+    pub layout: Option<Layout>,
+}
+
+/// A `DocumentRef` is a `Document` borrowing from the Markdown provided as
+/// input to `YamlFrontMatter::parse_ref`, avoiding copies of the front
+/// matter and the body.
+pub struct DocumentRef<'a, T> {
+    /// A generic type with the structure of the Markdown's
+    /// front matter header, which may borrow from the Markdown.
+    pub metadata: T,
+    /// The raw front matter between the fences, empty if the document has
+    /// no front matter
+    pub front_matter: &'a str,
+    /// The body of the Markdown following the closing fence, byte for byte
+    pub content: &'a str,
+    /// The format of the front matter header, `None` if the document has
+    /// no front matter
+    pub format: Option<Format>,
+    /// Where the front matter and the body live in the Markdown, `None` if
+    /// the document has no front matter
+    pub layout: Option<Layout>,
+}
+
+impl<T: Serialize> Document<T> {
+    /// Writes the document back as Markdown, emitting the `metadata` as YAML
+    /// between `---` fences followed by the `content`, so the body of a
+    /// parsed document is written back byte for byte.
+    ///
+    /// The front matter is always written as YAML regardless of `format`.
+    /// Metadata serializing to null, such as the `None` of a document parsed
+    /// without front matter, writes the `content` alone.
+    ///
+    /// A parsed document is written with the line ending of the closing
+    /// fence only if it was stripped from the body when parsing. For a
+    /// document built by hand, the `content` is taken to hold that line
+    /// ending if it starts with one.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), FrontMatterError> {
+        let metadata = serde_yaml::to_value(&self.metadata)
+            .map_err(|err| FrontMatterError::Serialize(Box::new(err)))?;
+
+        if metadata.is_null() {
+            writer.write_all(self.content.as_bytes())?;
+
+            return Ok(());
+        }
+
+        let yaml = serde_yaml::to_string(&metadata)
+            .map_err(|err| FrontMatterError::Serialize(Box::new(err)))?;
+
+        writer.write_all(b"---\n")?;
+        writer.write_all(yaml.as_bytes())?;
+
+        if !yaml.ends_with('\n') {
+            writer.write_all(b"\n")?;
+        }
+
+        writer.write_all(b"---")?;
+
+        let holds_fence_newline = match &self.layout {
+            Some(layout) => !layout.strips_fence_newline(),
+            None => self.content.starts_with('\n') || self.content.starts_with("\r\n"),
+        };
+
+        if !holds_fence_newline {
+            writer.write_all(b"\n")?;
+        }
+
+        writer.write_all(self.content.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Renders the document back as Markdown, see `Document::write_to`.
+    pub fn to_string(&self) -> Result<String, FrontMatterError> {
+        let mut markdown = Vec::new();
+
+        self.write_to(&mut markdown)?;
+
+        Ok(String::from_utf8(markdown).expect("YAML and content are valid UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    use super::Document;
+    use crate::{Parser, YamlFrontMatter};
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Metadata {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn renders_front_matter_and_content() {
+        let document = Document {
+            metadata: Metadata {
+                title: "Round trip".to_string(),
+                tags: vec!["rust".to_string()],
+            },
+            content: "\n# Round trip".to_string(),
+            format: None,
+            layout: None,
+        };
+
+        assert_eq!(
+            document.to_string().unwrap(),
+            "---\ntitle: Round trip\ntags:\n- rust\n---\n# Round trip"
+        );
+    }
+
+    #[test]
+    fn round_trips_parsed_document() {
+        let markdown = "---\ntitle: Original\ntags: [rust]\n---\n\n# Body";
+        let mut document = YamlFrontMatter::parse::<Metadata>(markdown).unwrap();
+
+        document.metadata.title = "Modified".to_string();
+
+        let reparsed = YamlFrontMatter::parse::<Metadata>(&document.to_string().unwrap()).unwrap();
+
+        assert_eq!(reparsed.metadata, document.metadata);
+        assert_eq!(reparsed.content, document.content);
+    }
+
+    #[test]
+    fn writes_content_alone_without_front_matter() {
+        let markdown = "# Title\n\n---\n\nProse\n";
+        let document = Parser::new()
+            .strict(true)
+            .parse::<Metadata>(markdown)
+            .unwrap();
+
+        assert!(document.metadata.is_none());
+        assert_eq!(document.to_string().unwrap(), markdown);
+    }
+
+    #[test]
+    fn round_trips_stripped_fence_newline() {
+        let markdown = "---\ntitle: a\ntags: []\n---\n\n# Body\n";
+        let document = Parser::new()
+            .strip_fence_newline(true)
+            .parse::<Metadata>(markdown)
+            .unwrap();
+
+        assert_eq!(document.content, "\n# Body\n");
+        assert_eq!(
+            document.to_string().unwrap(),
+            "---\ntitle: a\ntags: []\n---\n\n# Body\n"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+
+use crate::Location;
+
+/// A key defined more than once in the same mapping of a YAML front matter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateKey {
+    path: String,
+    first: Location,
+    second: Location,
+}
+
+impl DuplicateKey {
+    #[cfg(feature = "checks")]
+    pub(crate) fn new(path: String, first: Location, second: Location) -> DuplicateKey {
+        DuplicateKey {
+            path,
+            first,
+            second,
+        }
+    }
+
+    /// The dotted key path of the duplicate key, such as `author.name`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Where the key is first defined in the Markdown document.
+    pub fn first(&self) -> &Location {
+        &self.first
+    }
+
+    /// Where the key is defined again in the Markdown document.
+    pub fn second(&self) -> &Location {
+        &self.second
+    }
+}
+
+/// What a `Parser` does with keys defined more than once in the same
+/// mapping of a YAML front matter, set through `Parser::duplicate_key_policy`
+/// with the `checks` feature.
+///
+/// Keys are compared by the value they resolve to, so `1` and `'1'` are
+/// different keys. The policies keeping one definition still fail with
+/// `FrontMatterError::DuplicateKey` when a dropped definition defines an
+/// anchor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Leaves the key to the deserializer, which fails or keeps one of its
+    /// values depending on the type the front matter is parsed into. This is
+    /// the default, and the only policy which doesn't read the front matter
+    /// twice.
+    Deserializer,
+    /// Fails with `FrontMatterError::DuplicateKey`, pointing at both
+    /// definitions of the key.
+    Error,
+    /// Keeps the value of the last definition of the key as `KeepLast`
+    /// does, reporting the key as a `WarningCode::DuplicateKey` warning.
+    Warn,
+    /// Keeps the value of the first definition of the key.
+    KeepFirst,
+    /// Keeps the value of the last definition of the key.
+    KeepLast,
+}
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "duplicate key `{}` at {}, first defined at {}",
+            self.path, self.second, self.first
+        )
+    }
+}
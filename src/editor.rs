@@ -0,0 +1,455 @@
+use std::ops::Range;
+
+use serde::Serialize;
+
+use crate::parser::trim_line_ending;
+use crate::{Format, FrontMatterError, Location, Parser};
+
+/// Edits the top-level keys of a Markdown document's front matter in place.
+///
+/// Unlike parsing into a `Document` and writing it back, the `Editor`
+/// rewrites only the lines of the keys being edited, preserving comments,
+/// key order and quoting style of every other entry as well as the body of
+/// the document.
+///
+/// ```
+/// use yaml_front_matter::Editor;
+///
+/// let markdown = "---\n# Post settings\ntitle: 'Hello'\ncategories: [rust]\n---\n# Hello\n";
+/// let mut editor = Editor::new(markdown).unwrap();
+///
+/// editor.set("draft", false).unwrap();
+/// editor.rename("categories", "tags").unwrap();
+///
+/// assert_eq!(
+///     editor.as_str(),
+///     "---\n# Post settings\ntitle: 'Hello'\ntags: [rust]\ndraft: false\n---\n# Hello\n"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Editor {
+    markdown: String,
+    /// The byte range of the front matter, `None` if the document has none
+    span: Option<Range<usize>>,
+    newline: &'static str,
+}
+
+/// A top-level entry of the front matter
+struct Entry {
+    /// The key as written in the document, including quotes
+    key: Range<usize>,
+    /// The key line along with the lines of its value
+    lines: Range<usize>,
+}
+
+impl Editor {
+    /// Creates an `Editor` for the provided Markdown, finding the front
+    /// matter in `strict` mode past any leading blank lines.
+    ///
+    /// Unlike the default `Parser`, the text between two thematic breaks
+    /// (`---`) of a document without front matter is not mistaken for front
+    /// matter, so such a document gets a front matter added instead of its
+    /// body being edited.
+    pub fn new(markdown: &str) -> Result<Editor, FrontMatterError> {
+        let parser = Parser::new().strict(true).allow_leading_blank_lines(true);
+
+        Editor::with_parser(markdown, &parser)
+    }
+
+    /// Creates an `Editor` for the provided Markdown using `parser` to find
+    /// the front matter.
+    ///
+    /// Only YAML front matter holding a block mapping, or nothing but
+    /// comments, can be edited. Other front matter, such as a flow mapping
+    /// between braces, fails with `FrontMatterError::NotBlockMapping`.
+    pub fn with_parser(markdown: &str, parser: &Parser) -> Result<Editor, FrontMatterError> {
+        let span = match parser.extract(markdown)? {
+            Some(extracted) if extracted.format != Format::Yaml => {
+                return Err(FrontMatterError::UnsupportedFormat(extracted.format))
+            }
+            extracted => extracted.map(|extracted| extracted.span),
+        };
+
+        if let Some(span) = span.clone() {
+            check_block_mapping(markdown, span)?;
+        }
+
+        let newline = if markdown.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        Ok(Editor {
+            markdown: markdown.to_string(),
+            span,
+            newline,
+        })
+    }
+
+    /// The raw front matter being edited, `None` if the document has none.
+    pub fn front_matter(&self) -> Option<&str> {
+        self.span.clone().map(|span| &self.markdown[span])
+    }
+
+    /// The edited Markdown document.
+    pub fn as_str(&self) -> &str {
+        &self.markdown
+    }
+
+    /// Consumes the `Editor` returning the edited Markdown document.
+    pub fn into_string(self) -> String {
+        self.markdown
+    }
+
+    /// Sets the top-level `key` to `value`, replacing the previous value if
+    /// the key exists or appending the key to the front matter otherwise.
+    ///
+    /// A front matter is added to the top of the document if it has none.
+    pub fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), FrontMatterError> {
+        let value = self.render_value(&value)?;
+
+        match self.find(key) {
+            Some(entry) => {
+                let key = self.markdown[entry.key.clone()].to_string();
+
+                self.replace(entry.lines, &format!("{}:{}", key, value));
+            }
+            None => {
+                let entry = format!("{}:{}", self.render_key(key)?, value);
+
+                match self.span.clone() {
+                    Some(span) => self.replace(span.end..span.end, &entry),
+                    None => {
+                        let fence = format!("---{}", self.newline);
+
+                        self.markdown
+                            .insert_str(0, &format!("{}{}{}", fence, entry, fence));
+                        self.span = Some(fence.len()..fence.len() + entry.len());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames the top-level key `from` to `to`, leaving its value untouched.
+    ///
+    /// Returns `false` if the front matter has no `from` key, and fails with
+    /// `FrontMatterError::KeyExists` if it already has a `to` key rather
+    /// than defining the key twice.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<bool, FrontMatterError> {
+        let entry = match self.find(from) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+
+        if from != to {
+            if let Some(existing) = self.find(to) {
+                return Err(FrontMatterError::KeyExists {
+                    key: to.to_string(),
+                    location: Location::at(&self.markdown, existing.key.start),
+                });
+            }
+        }
+
+        let to = self.render_key(to)?;
+
+        self.replace(entry.key, &to);
+
+        Ok(true)
+    }
+
+    /// Removes the top-level `key` along with its value.
+    ///
+    /// Returns `false` if the front matter has no such key.
+    pub fn remove(&mut self, key: &str) -> bool {
+        match self.find(key) {
+            Some(entry) => {
+                self.replace(entry.lines, "");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the bytes in `range`, which must be within the front matter,
+    /// keeping track of the front matter span.
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        let removed = range.end - range.start;
+
+        self.markdown.replace_range(range, text);
+
+        if let Some(span) = self.span.as_mut() {
+            span.end = span.end + text.len() - removed;
+        }
+    }
+
+    /// Finds the top-level entry for `key`.
+    fn find(&self, key: &str) -> Option<Entry> {
+        let span = self.span.clone()?;
+        let mut offset = span.start;
+        let mut found: Option<Entry> = None;
+        let texts = self.markdown[span]
+            .split_inclusive('\n')
+            .collect::<Vec<_>>();
+        let lines = texts
+            .iter()
+            .map(|text| trim_line_ending(text))
+            .collect::<Vec<_>>();
+        let is_indented = |line: &str| {
+            line.trim().is_empty() || line.starts_with(char::is_whitespace) || line.starts_with('-')
+        };
+
+        for (index, (text, line)) in texts.iter().zip(lines.iter()).enumerate() {
+            // A comment at the start of a line belongs to the entry if the
+            // value of the entry goes on after it
+            let is_continuation = is_indented(line)
+                || line.starts_with('#')
+                    && lines[index + 1..]
+                        .iter()
+                        .find(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                        .is_some_and(|line| is_indented(line));
+
+            match found.as_mut() {
+                Some(entry) if is_continuation => {
+                    if !line.trim().is_empty() {
+                        entry.lines.end = offset + text.len();
+                    }
+                }
+                Some(_) => break,
+                None => {
+                    if let Some((raw, unquoted)) = parse_key(line) {
+                        if unquoted == key {
+                            found = Some(Entry {
+                                key: offset..offset + raw.len(),
+                                lines: offset..offset + text.len(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            offset += text.len();
+        }
+
+        found
+    }
+
+    /// Renders `value` as the YAML following a key, including the separating
+    /// space or line break and the trailing line ending.
+    fn render_value<V: Serialize>(&self, value: &V) -> Result<String, FrontMatterError> {
+        let value = serde_yaml::to_value(value)
+            .map_err(|err| FrontMatterError::Serialize(Box::new(err)))?;
+        let yaml = render(&value)?;
+
+        match value {
+            serde_yaml::Value::Sequence(ref items) if !items.is_empty() => {}
+            serde_yaml::Value::Mapping(ref entries) if !entries.is_empty() => {}
+            _ => return Ok(format!(" {}{}", yaml.trim_end(), self.newline)),
+        }
+
+        Ok(yaml
+            .lines()
+            .fold(self.newline.to_string(), |rendered, line| {
+                format!("{}  {}{}", rendered, line, self.newline)
+            }))
+    }
+
+    fn render_key(&self, key: &str) -> Result<String, FrontMatterError> {
+        Ok(render(&key)?.trim_end().to_string())
+    }
+}
+
+/// Fails unless the first line of the front matter in `span` which is not
+/// blank or a comment starts a top-level entry of a block mapping.
+fn check_block_mapping(markdown: &str, span: Range<usize>) -> Result<(), FrontMatterError> {
+    let mut offset = span.start;
+
+    for text in markdown[span].split_inclusive('\n') {
+        let line = trim_line_ending(text);
+        let content = line.trim_start();
+
+        if !content.is_empty() && !content.starts_with('#') {
+            return match parse_key(line) {
+                Some(_) => Ok(()),
+                None => Err(FrontMatterError::NotBlockMapping {
+                    location: Location::at(markdown, offset + line.len() - content.len()),
+                }),
+            };
+        }
+
+        offset += text.len();
+    }
+
+    Ok(())
+}
+
+/// Serializes `value` as YAML.
+fn render<V: Serialize>(value: &V) -> Result<String, FrontMatterError> {
+    serde_yaml::to_string(value).map_err(|err| FrontMatterError::Serialize(Box::new(err)))
+}
+
+/// Parses the key of a top-level mapping entry line, returning the key as
+/// written and the key without quotes.
+fn parse_key(line: &str) -> Option<(&str, String)> {
+    let first = line.chars().next()?;
+
+    if first.is_whitespace() || matches!(first, '#' | '-' | '?' | '{' | '[') {
+        return None;
+    }
+
+    let raw = match first {
+        '"' | '\'' => {
+            let end = line[1..].find(first)? + 2;
+
+            &line[..end]
+        }
+        _ => {
+            let end = line
+                .match_indices(':')
+                .map(|(index, _)| index)
+                .find(|index| {
+                    matches!(line[index + 1..].chars().next(), None | Some(' ' | '\t'))
+                })?;
+
+            line[..end].trim_end()
+        }
+    };
+
+    if !line[raw.len()..].trim_start().starts_with(':') {
+        return None;
+    }
+
+    let unquoted = match first {
+        '"' | '\'' => serde_yaml::from_str::<String>(raw).ok()?,
+        _ => raw.to_string(),
+    };
+
+    Some((raw, unquoted))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Editor;
+    use crate::FrontMatterError;
+
+    const MARKDOWN: &str = r#"---
+# Generated by the blog importer
+title: "Hello, World"
+'categories':
+  - rust
+  - tutorial
+
+draft: yes # reviewed
+---
+# Hello, World
+"#;
+
+    #[test]
+    fn replaces_existing_key() {
+        let mut editor = Editor::new(MARKDOWN).unwrap();
+
+        editor.set("draft", false).unwrap();
+
+        assert_eq!(
+            editor.as_str(),
+            MARKDOWN.replace("draft: yes # reviewed", "draft: false")
+        );
+    }
+
+    #[test]
+    fn replaces_multiline_value() {
+        let mut editor = Editor::new(MARKDOWN).unwrap();
+
+        editor.set("categories", vec!["news"]).unwrap();
+
+        assert_eq!(
+            editor.as_str(),
+            MARKDOWN.replace("  - rust\n  - tutorial\n", "  - news\n")
+        );
+    }
+
+    #[test]
+    fn renames_key_keeping_value() {
+        let mut editor = Editor::new(MARKDOWN).unwrap();
+
+        assert!(editor.rename("categories", "tags").unwrap());
+        assert!(!editor.rename("missing", "tags").unwrap());
+        assert_eq!(editor.as_str(), MARKDOWN.replace("'categories':", "tags:"));
+    }
+
+    #[test]
+    fn refuses_to_rename_onto_existing_key() {
+        let mut editor = Editor::new(MARKDOWN).unwrap();
+        let err = editor.rename("categories", "draft").unwrap_err();
+
+        assert!(matches!(err, FrontMatterError::KeyExists { .. }));
+        assert_eq!(err.location().unwrap().line(), 8);
+        assert_eq!(editor.as_str(), MARKDOWN);
+    }
+
+    #[test]
+    fn removes_key_and_value() {
+        let mut editor = Editor::new(MARKDOWN).unwrap();
+
+        assert!(editor.remove("categories"));
+        assert_eq!(
+            editor.as_str(),
+            MARKDOWN.replace("'categories':\n  - rust\n  - tutorial\n", "")
+        );
+    }
+
+    #[test]
+    fn keeps_comments_inside_values_with_their_entry() {
+        let markdown = "---\ntags:\n# note\n  - a\n# Next\ntitle: a\n---\n";
+        let mut editor = Editor::new(markdown).unwrap();
+
+        assert!(editor.remove("tags"));
+        assert_eq!(editor.as_str(), "---\n# Next\ntitle: a\n---\n");
+
+        let mut editor = Editor::new(markdown).unwrap();
+
+        editor.set("tags", vec!["b"]).unwrap();
+        assert_eq!(
+            editor.as_str(),
+            "---\ntags:\n  - b\n# Next\ntitle: a\n---\n"
+        );
+    }
+
+    #[test]
+    fn refuses_to_edit_flow_mappings() {
+        let err = Editor::new("---\n# Flow\n{title: a}\n---\n").unwrap_err();
+
+        assert!(matches!(err, FrontMatterError::NotBlockMapping { .. }));
+        assert_eq!(err.location().unwrap().line(), 3);
+        assert!(Editor::new("---\n# Only comments\n---\n").is_ok());
+    }
+
+    #[test]
+    fn adds_front_matter_when_missing() {
+        let mut editor = Editor::new("# Hello\n").unwrap();
+
+        editor.set("title", "Hello").unwrap();
+        editor.set("draft", true).unwrap();
+
+        assert_eq!(
+            editor.as_str(),
+            "---\ntitle: Hello\ndraft: true\n---\n# Hello\n"
+        );
+    }
+
+    #[test]
+    fn leaves_thematic_breaks_of_body_untouched() {
+        let markdown = "# Hello\n\nIntro\n\n---\n\nMiddle\n\n---\n\nEnd\n";
+        let mut editor = Editor::new(markdown).unwrap();
+
+        editor.set("draft", false).unwrap();
+
+        assert_eq!(
+            editor.as_str(),
+            format!("---\ndraft: false\n---\n{}", markdown)
+        );
+    }
+}
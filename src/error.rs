@@ -0,0 +1,275 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::{DuplicateKey, Format, Location, Violation, Warning};
+
+/// Errors produced while extracting and parsing the front matter of a
+/// Markdown document.
+#[derive(Debug)]
+pub enum FrontMatterError {
+    /// The document has no opening fence, so there is no front matter to
+    /// parse.
+    MissingOpeningFence,
+    /// An opening fence was found but it is never closed.
+    MissingClosingFence {
+        /// The line of the opening fence in the Markdown document.
+        line: usize,
+        /// Where the opening fence is in the Markdown document.
+        location: Option<Location>,
+    },
+    /// The front matter is not valid in its format.
+    Syntax {
+        /// The format of the front matter.
+        format: Format,
+        /// The message of the YAML, TOML or JSON library, without the
+        /// position it reports relative to the front matter.
+        message: String,
+        source: Box<dyn Error + Send + Sync>,
+        /// Where the error occurred in the Markdown document, if known.
+        location: Option<Location>,
+    },
+    /// The front matter is valid in its format but it doesn't match the
+    /// structure of the requested type.
+    Deserialize {
+        /// The format of the front matter.
+        format: Format,
+        /// The message of the YAML, TOML or JSON library, without the
+        /// position it reports relative to the front matter.
+        message: String,
+        source: Box<dyn Error + Send + Sync>,
+        /// Where the error occurred in the Markdown document, if known.
+        location: Option<Location>,
+    },
+    /// A key is defined more than once in the same mapping of the YAML
+    /// front matter.
+    DuplicateKey(Box<DuplicateKey>),
+    /// A warning whose code is promoted to an error through
+    /// `Parser::promote_warning`.
+    Warning(Box<Warning>),
+    /// A key is renamed to a key the front matter already has.
+    KeyExists {
+        /// The key the front matter already has.
+        key: String,
+        /// Where the key is in the Markdown document.
+        location: Option<Location>,
+    },
+    /// The operation is not supported for front matter in this format.
+    UnsupportedFormat(Format),
+    /// The front matter to edit is not a block mapping, such as a flow
+    /// mapping between braces.
+    NotBlockMapping {
+        /// Where the front matter content starts in the Markdown document.
+        location: Option<Location>,
+    },
+    /// The metadata could not be serialized, either as YAML to be written
+    /// back or as JSON to be validated against a schema.
+    Serialize(Box<dyn Error + Send + Sync>),
+    /// An I/O error occurred while reading or writing the document.
+    Io(io::Error),
+    /// The glob pattern selecting documents is not valid.
+    Pattern {
+        /// The glob pattern.
+        pattern: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// The JSON Schema to validate front matter against is not valid.
+    InvalidSchema(Box<dyn Error + Send + Sync>),
+    /// The front matter doesn't match the JSON Schema, along with every
+    /// violation of the schema.
+    Validation(Vec<Violation>),
+    /// An error occurred while reading or parsing the document at `path`.
+    File {
+        /// The path of the document.
+        path: PathBuf,
+        /// The error which occurred, never a `File` error itself.
+        source: Box<FrontMatterError>,
+    },
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrontMatterError::MissingOpeningFence => {
+                write!(f, "missing opening front matter fence")
+            }
+            FrontMatterError::MissingClosingFence { line, .. } => write!(
+                f,
+                "missing closing front matter fence for the fence opened at line {}",
+                line
+            ),
+            FrontMatterError::Syntax {
+                format, location, ..
+            } => {
+                write!(f, "front matter is not valid {}", format)?;
+                write_location(f, location)
+            }
+            FrontMatterError::Deserialize {
+                format, location, ..
+            } => {
+                write!(f, "failed to deserialize {} front matter", format)?;
+                write_location(f, location)
+            }
+            FrontMatterError::DuplicateKey(duplicate) => {
+                write!(f, "front matter has a {}", duplicate)
+            }
+            FrontMatterError::Warning(warning) => write!(f, "{}", warning),
+            FrontMatterError::KeyExists { key, location } => {
+                write!(f, "front matter already has a `{}` key", key)?;
+                write_location(f, location)
+            }
+            FrontMatterError::UnsupportedFormat(format) => {
+                write!(f, "{} front matter is not supported", format)
+            }
+            FrontMatterError::NotBlockMapping { location } => {
+                write!(f, "front matter is not a block mapping")?;
+                write_location(f, location)
+            }
+            FrontMatterError::Serialize(_) => write!(f, "failed to serialize front matter"),
+            FrontMatterError::Io(_) => write!(f, "failed to read or write document"),
+            FrontMatterError::Pattern { pattern, .. } => {
+                write!(f, "invalid glob pattern `{}`", pattern)
+            }
+            FrontMatterError::InvalidSchema(_) => write!(f, "invalid JSON Schema"),
+            FrontMatterError::Validation(violations) => {
+                write!(f, "front matter does not match the schema")?;
+
+                violations
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(index, violation)| {
+                        let separator = if index == 0 { ": " } else { "; " };
+
+                        write!(f, "{}{}", separator, violation)
+                    })
+            }
+            FrontMatterError::File { path, source } => {
+                write!(f, "{}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for FrontMatterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FrontMatterError::MissingOpeningFence
+            | FrontMatterError::MissingClosingFence { .. }
+            | FrontMatterError::DuplicateKey(_)
+            | FrontMatterError::Warning(_)
+            | FrontMatterError::KeyExists { .. }
+            | FrontMatterError::UnsupportedFormat(_)
+            | FrontMatterError::NotBlockMapping { .. } => None,
+            FrontMatterError::Validation(_) => None,
+            FrontMatterError::InvalidSchema(source) => Some(source.as_ref()),
+            FrontMatterError::Syntax { source, .. }
+            | FrontMatterError::Deserialize { source, .. }
+            | FrontMatterError::Pattern { source, .. } => Some(source.as_ref()),
+            FrontMatterError::Serialize(source) => Some(source.as_ref()),
+            FrontMatterError::Io(err) => Some(err),
+            // The message of the wrapped error is part of this one
+            FrontMatterError::File { source, .. } => source.source(),
+        }
+    }
+}
+
+impl FrontMatterError {
+    /// The location in the Markdown document where the error occurred, if
+    /// known.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            FrontMatterError::MissingClosingFence { location, .. }
+            | FrontMatterError::Syntax { location, .. }
+            | FrontMatterError::Deserialize { location, .. }
+            | FrontMatterError::KeyExists { location, .. }
+            | FrontMatterError::NotBlockMapping { location } => location.as_ref(),
+            FrontMatterError::DuplicateKey(duplicate) => Some(duplicate.second()),
+            FrontMatterError::Warning(warning) => warning.location(),
+            FrontMatterError::Validation(violations) => {
+                violations.iter().find_map(|violation| violation.location())
+            }
+            FrontMatterError::File { source, .. } => source.location(),
+            _ => None,
+        }
+    }
+
+    /// The message of the YAML, TOML or JSON library behind a `Syntax` or
+    /// `Deserialize` error, without the position it reports relative to the
+    /// front matter, which `location` maps to the Markdown document.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            FrontMatterError::Syntax { message, .. }
+            | FrontMatterError::Deserialize { message, .. } => Some(message),
+            FrontMatterError::File { source, .. } => source.message(),
+            _ => None,
+        }
+    }
+
+    /// Attaches the `path` of the document to the error.
+    pub(crate) fn in_file(self, path: PathBuf) -> Self {
+        match self {
+            FrontMatterError::File { .. } => self,
+            err => FrontMatterError::File {
+                path,
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
+fn write_location(f: &mut fmt::Formatter<'_>, location: &Option<Location>) -> fmt::Result {
+    match location {
+        Some(location) => write!(f, " at {}", location),
+        None => Ok(()),
+    }
+}
+
+impl From<io::Error> for FrontMatterError {
+    fn from(err: io::Error) -> Self {
+        FrontMatterError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use super::FrontMatterError;
+    use crate::Format;
+
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+        assert_send_sync::<FrontMatterError>();
+    }
+
+    #[test]
+    fn exposes_source_error() {
+        let yaml_err = serde_yaml::from_str::<serde_yaml::Value>("key: [").unwrap_err();
+        let err = FrontMatterError::Syntax {
+            format: Format::Yaml,
+            message: yaml_err.to_string(),
+            source: Box::new(yaml_err),
+            location: None,
+        };
+
+        assert!(err.source().is_some());
+        assert!(FrontMatterError::MissingOpeningFence.source().is_none());
+    }
+
+    #[test]
+    fn names_file_in_message() {
+        let err = FrontMatterError::MissingOpeningFence.in_file("posts/hello.md".into());
+
+        assert_eq!(
+            err.to_string(),
+            "posts/hello.md: missing opening front matter fence"
+        );
+        assert!(matches!(
+            err.in_file("other.md".into()),
+            FrontMatterError::File { path, .. } if path.ends_with("hello.md")
+        ));
+    }
+}
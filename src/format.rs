@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::de::Deserialize;
+
+/// The language of a front matter, told apart by its fences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// YAML front matter between `---` fences.
+    Yaml,
+    /// TOML front matter between `+++` fences, as used by Hugo and Zola.
+    ///
+    /// Requires the `toml` feature.
+    Toml,
+    /// JSON front matter, either as a bare object at the top of the
+    /// document or between `;;;` fences.
+    ///
+    /// Requires the `json` feature.
+    Json,
+}
+
+impl Format {
+    /// The line opening and closing a front matter in this format.
+    pub fn fence(self) -> &'static str {
+        match self {
+            Format::Yaml => "---",
+            Format::Toml => "+++",
+            Format::Json => ";;;",
+        }
+    }
+
+    /// Finds the format opened by a fence along with the fence closing it,
+    /// considering only the formats enabled by cargo features.
+    ///
+    /// Besides each format's own fence, `---` followed by a language hint
+    /// such as `---toml` is recognized and closed by `---`.
+    pub(crate) fn from_fence(fence: &str) -> Option<(Format, &'static str)> {
+        let format = match fence {
+            "---" | "---yaml" | "---yml" => Format::Yaml,
+            #[cfg(feature = "toml")]
+            "+++" | "---toml" => Format::Toml,
+            #[cfg(feature = "json")]
+            ";;;" | "---json" => Format::Json,
+            _ => return None,
+        };
+        let closing = match fence.strip_prefix("---") {
+            Some(_) => "---",
+            None => format.fence(),
+        };
+
+        Some((format, closing))
+    }
+
+    /// Deserializes a front matter in this format into `T`.
+    pub(crate) fn deserialize<'a, T: Deserialize<'a>>(
+        self,
+        front_matter: &'a str,
+    ) -> Result<T, BackendError> {
+        match self {
+            Format::Yaml => serde_yaml::from_str::<T>(front_matter).map_err(|err| {
+                match serde_yaml::from_str::<serde_yaml::Value>(front_matter) {
+                    Ok(_) => BackendError::yaml(BackendErrorKind::Deserialize, err),
+                    Err(syntax_err) => BackendError::yaml(BackendErrorKind::Syntax, syntax_err),
+                }
+            }),
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::from_str::<T>(front_matter).map_err(|err| {
+                match toml::from_str::<toml::Value>(front_matter) {
+                    Ok(_) => BackendError::toml(BackendErrorKind::Deserialize, err),
+                    Err(syntax_err) => BackendError::toml(BackendErrorKind::Syntax, syntax_err),
+                }
+            }),
+            #[cfg(not(feature = "toml"))]
+            Format::Toml => unreachable!("TOML front matter requires the `toml` feature"),
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::from_str::<T>(front_matter).map_err(|err| {
+                match serde_json::from_str::<serde_json::Value>(front_matter) {
+                    Ok(_) => BackendError::json(BackendErrorKind::Deserialize, err),
+                    Err(syntax_err) => BackendError::json(BackendErrorKind::Syntax, syntax_err),
+                }
+            }),
+            #[cfg(not(feature = "json"))]
+            Format::Json => unreachable!("JSON front matter requires the `json` feature"),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Yaml => write!(f, "YAML"),
+            Format::Toml => write!(f, "TOML"),
+            Format::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// Whether a backend failed to parse the front matter or to map it into the
+/// requested type
+pub(crate) enum BackendErrorKind {
+    Syntax,
+    Deserialize,
+}
+
+/// An error reported by the library deserializing a front matter
+pub(crate) struct BackendError {
+    pub(crate) kind: BackendErrorKind,
+    /// The 1-based line and column relative to the front matter
+    pub(crate) position: Option<(usize, usize)>,
+    /// The message of the error without its position
+    pub(crate) message: String,
+    pub(crate) source: Box<dyn Error + Send + Sync>,
+}
+
+impl BackendError {
+    fn yaml(kind: BackendErrorKind, err: serde_yaml::Error) -> Self {
+        let position = err
+            .location()
+            .map(|location| (location.line(), location.column()));
+
+        BackendError::new(kind, position, err)
+    }
+
+    #[cfg(feature = "toml")]
+    fn toml(kind: BackendErrorKind, err: toml::de::Error) -> Self {
+        let position = err.line_col().map(|(line, column)| (line + 1, column + 1));
+
+        BackendError::new(kind, position, err)
+    }
+
+    #[cfg(feature = "json")]
+    pub(crate) fn json(kind: BackendErrorKind, err: serde_json::Error) -> Self {
+        let position = match err.line() {
+            0 => None,
+            line => Some((line, err.column())),
+        };
+
+        BackendError::new(kind, position, err)
+    }
+
+    /// Wraps `err`, reported at the 1-based `position` in the front matter,
+    /// which the YAML, TOML and JSON libraries all append to their message
+    /// as ` at line L column C`.
+    fn new<E: Error + Send + Sync + 'static>(
+        kind: BackendErrorKind,
+        position: Option<(usize, usize)>,
+        err: E,
+    ) -> Self {
+        let message = err.to_string();
+        let message = match position {
+            Some((line, column)) => {
+                message.replacen(&format!(" at line {} column {}", line, column), "", 1)
+            }
+            None => message,
+        };
+
+        BackendError {
+            kind,
+            position,
+            message,
+            source: Box::new(err),
+        }
+    }
+}
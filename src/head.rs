@@ -0,0 +1,19 @@
+use crate::Format;
+
+/// The front matter of a Markdown document read up to its closing fence,
+/// leaving the body unread.
+///
+/// Returned by `read_head`, which suits listings that only need the
+/// metadata of large documents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Head<T> {
+    /// A generic type with the structure of the Markdown's
+    /// front matter header.
+    pub metadata: T,
+    /// The format of the front matter header, `None` if the document has
+    /// no front matter
+    pub format: Option<Format>,
+    /// The byte offset where the body begins in the document, `0` if the
+    /// document has no front matter
+    pub body_offset: usize,
+}
@@ -0,0 +1,41 @@
+/// The front matter header of a Markdown document which may not have one.
+///
+/// Returned as the `metadata` of a `Document` by `parse_optional`, telling
+/// apart documents without front matter from documents with an empty front
+/// matter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Header<T> {
+    /// The document has no front matter.
+    Absent,
+    /// The document has front matter fences but no YAML between them, other
+    /// than blank lines and comments.
+    Empty,
+    /// The document has front matter with data.
+    Present(T),
+}
+
+impl<T> Header<T> {
+    /// Returns `true` if the document has no front matter.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Header::Absent)
+    }
+
+    /// Returns `true` if the document has an empty front matter.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Header::Empty)
+    }
+
+    /// Returns `true` if the document has front matter with data.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Header::Present(_))
+    }
+
+    /// Converts the header into an `Option`, which is `Some` only when the
+    /// front matter has data.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Header::Present(metadata) => Some(metadata),
+            Header::Absent | Header::Empty => None,
+        }
+    }
+}
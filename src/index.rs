@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use serde_yaml::value::{Tag, TaggedValue};
+use serde_yaml::Value;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser as YamlParser};
+use yaml_rust::scanner::{Marker, TScalarStyle, TokenType};
+
+/// The 1-based line and column of a YAML node relative to the front matter
+pub(crate) type Position = (usize, usize);
+
+/// Where the nodes of a YAML front matter and the keys of its mappings are,
+/// indexed by their JSON pointer, along with the mapping keys defined more
+/// than once.
+#[derive(Debug, Default)]
+pub(crate) struct YamlIndex {
+    pub(crate) positions: HashMap<String, Position>,
+    /// Where the first definition of every mapping key is
+    pub(crate) keys: HashMap<String, Position>,
+    pub(crate) duplicates: Vec<DuplicateEntry>,
+    /// The 1-based lines of the content of block scalars, relative to the
+    /// front matter
+    pub(crate) block_scalars: Vec<Range<usize>>,
+    containers: Vec<Container>,
+    /// The first line of the block scalar just read, which ends where the
+    /// next node starts
+    block_scalar: Option<usize>,
+}
+
+/// A mapping key defined more than once
+#[derive(Debug)]
+pub(crate) struct DuplicateEntry {
+    /// The JSON pointer to the entry
+    pub(crate) path: String,
+    /// Where the key is defined first
+    pub(crate) first: Position,
+    /// Where the key is defined again
+    pub(crate) second: Position,
+    /// The byte range of the entry defining the key first, from its key up
+    /// to the next entry of the mapping
+    pub(crate) first_entry: Range<usize>,
+    /// The byte range of the entry defining the key again
+    pub(crate) second_entry: Range<usize>,
+    /// Whether the entry defining the key first defines an anchor
+    pub(crate) first_anchored: bool,
+    /// Whether the entry defining the key again defines an anchor
+    pub(crate) second_anchored: bool,
+}
+
+/// An entry of a mapping
+#[derive(Debug)]
+struct Entry {
+    /// Where the key of the entry is
+    position: Position,
+    /// The character range of the entry, up to the next entry
+    chars: Range<usize>,
+    /// Whether an anchor is defined anywhere in the entry
+    anchored: bool,
+}
+
+/// A YAML node holding other nodes, along with its JSON pointer
+#[derive(Debug)]
+enum Container {
+    /// The key of the entry being read, `None` while reading a key, the
+    /// first entry of every key read so far by resolved value and the
+    /// entries defining a key again, along with the first entry of the key
+    Mapping {
+        path: String,
+        key: Option<String>,
+        keys: HashMap<Value, usize>,
+        entries: Vec<Entry>,
+        duplicates: Vec<(String, usize, usize)>,
+    },
+    /// The index of the item being read
+    Sequence { path: String, index: usize },
+}
+
+impl YamlIndex {
+    /// Indexes the nodes of the `yaml`, `None` if it is not valid YAML.
+    pub(crate) fn new(yaml: &str) -> Option<YamlIndex> {
+        let mut index = YamlIndex::default();
+
+        YamlParser::new(yaml.chars()).load(&mut index, false).ok()?;
+
+        // The parser marks characters, not bytes
+        let chars = yaml.chars().collect::<Vec<_>>();
+        let offsets = yaml
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(Some(yaml.len()))
+            .collect::<Vec<_>>();
+
+        for duplicate in index.duplicates.iter_mut() {
+            let bytes = |range: &Range<usize>| {
+                offsets[properties(&chars, range.start)]..offsets[properties(&chars, range.end)]
+            };
+
+            duplicate.first_entry = bytes(&duplicate.first_entry);
+            duplicate.second_entry = bytes(&duplicate.second_entry);
+        }
+
+        index.duplicates.sort_by_key(|duplicate| duplicate.second);
+
+        Some(index)
+    }
+
+    /// Moves on to the next entry or item of the innermost container.
+    fn advance(&mut self) {
+        match self.containers.last_mut() {
+            Some(Container::Mapping { key, .. }) => *key = None,
+            Some(Container::Sequence { index, .. }) => *index += 1,
+            None => {}
+        }
+    }
+
+    /// Marks the entries being read as defining an anchor.
+    fn anchor(&mut self) {
+        for container in self.containers.iter_mut() {
+            if let Container::Mapping { entries, .. } = container {
+                if let Some(entry) = entries.last_mut() {
+                    entry.anchored = true;
+                }
+            }
+        }
+    }
+}
+
+impl MarkedEventReceiver for YamlIndex {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let position = (mark.line(), mark.col() + 1);
+
+        if let Some(start) = self.block_scalar.take() {
+            self.block_scalars.push(start..mark.line());
+        }
+
+        if let Event::Scalar(_, TScalarStyle::Literal | TScalarStyle::Foled, ..) = event {
+            self.block_scalar = Some(mark.line());
+        }
+
+        let anchored = match event {
+            Event::Scalar(_, _, anchor, _)
+            | Event::MappingStart(anchor)
+            | Event::SequenceStart(anchor) => anchor > 0,
+            _ => false,
+        };
+
+        match event {
+            Event::Scalar(..)
+            | Event::Alias(_)
+            | Event::MappingStart(_)
+            | Event::SequenceStart(_) => {
+                if let Some(Container::Mapping {
+                    path,
+                    key: key @ None,
+                    keys,
+                    entries,
+                    duplicates,
+                }) = self.containers.last_mut()
+                {
+                    if let Event::Scalar(value, style, _, tag) = &event {
+                        match entries.last_mut() {
+                            Some(entry) => entry.chars.end = mark.index(),
+                            // Block mappings are marked past the key of their
+                            // first entry, point at the key instead
+                            None => {
+                                self.positions.insert(path.clone(), position);
+                            }
+                        }
+
+                        entries.push(Entry {
+                            position,
+                            chars: mark.index()..mark.index(),
+                            anchored: false,
+                        });
+
+                        match keys.get(&resolve(value, *style, tag.as_ref())) {
+                            Some(first) => {
+                                duplicates.push((pointer(path, value), *first, entries.len() - 1))
+                            }
+                            None => {
+                                keys.insert(
+                                    resolve(value, *style, tag.as_ref()),
+                                    entries.len() - 1,
+                                );
+                                self.keys.entry(pointer(path, value)).or_insert(position);
+                            }
+                        }
+
+                        *key = Some(value.clone());
+
+                        if anchored {
+                            self.anchor();
+                        }
+
+                        return;
+                    }
+                }
+
+                if anchored {
+                    self.anchor();
+                }
+
+                let path = match self.containers.last() {
+                    Some(Container::Mapping { path, key, .. }) => {
+                        pointer(path, key.as_deref().unwrap_or_default())
+                    }
+                    Some(Container::Sequence { path, index }) => format!("{}/{}", path, index),
+                    None => String::new(),
+                };
+
+                self.positions.insert(path.clone(), position);
+
+                match event {
+                    Event::MappingStart(_) => self.containers.push(Container::Mapping {
+                        path,
+                        key: None,
+                        keys: HashMap::new(),
+                        entries: Vec::new(),
+                        duplicates: Vec::new(),
+                    }),
+                    Event::SequenceStart(_) => {
+                        self.containers.push(Container::Sequence { path, index: 0 })
+                    }
+                    _ => self.advance(),
+                }
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if let Some(Container::Mapping {
+                    mut entries,
+                    duplicates,
+                    ..
+                }) = self.containers.pop()
+                {
+                    if let Some(entry) = entries.last_mut() {
+                        entry.chars.end = mark.index();
+                    }
+
+                    for (path, first, second) in duplicates {
+                        self.duplicates.push(DuplicateEntry {
+                            path,
+                            first: entries[first].position,
+                            second: entries[second].position,
+                            first_entry: entries[first].chars.clone(),
+                            second_entry: entries[second].chars.clone(),
+                            first_anchored: entries[first].anchored,
+                            second_anchored: entries[second].anchored,
+                        });
+                    }
+                }
+
+                self.advance();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Moves the character `index` of a node back to the tag and anchor before
+/// it, which the parser marks past.
+fn properties(chars: &[char], mut index: usize) -> usize {
+    loop {
+        let blank = chars[..index]
+            .iter()
+            .rev()
+            .take_while(|c| **c == ' ' || **c == '\t')
+            .count();
+        let token = chars[..index - blank]
+            .iter()
+            .rev()
+            .take_while(|c| !c.is_whitespace() && !matches!(c, ',' | '[' | '{'))
+            .count();
+
+        match chars.get(index - blank - token) {
+            Some('!' | '&') if blank > 0 && token > 0 => index -= blank + token,
+            _ => return index,
+        }
+    }
+}
+
+/// Resolves a scalar mapping key to the value it stands for, telling `1` and
+/// `'1'` apart the way the YAML deserializer does.
+fn resolve(value: &str, style: TScalarStyle, tag: Option<&TokenType>) -> Value {
+    let string = || Value::String(value.to_string());
+
+    match tag {
+        Some(TokenType::Tag(handle, suffix)) => match (handle.as_str(), suffix.as_str()) {
+            ("!!", "str") | ("!", "") => string(),
+            _ => Value::Tagged(Box::new(TaggedValue {
+                tag: Tag::new(format!("{}{}", handle, suffix)),
+                value: string(),
+            })),
+        },
+        _ if style != TScalarStyle::Plain => string(),
+        _ => serde_yaml::from_str(value).unwrap_or_else(|_| string()),
+    }
+}
+
+/// Appends the mapping `key` to the JSON pointer `path`.
+fn pointer(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Turns a JSON pointer into a dotted key path, such as `author.name`.
+pub(crate) fn dotted(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|key| key.replace("~1", "/").replace("~0", "~"))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dotted, YamlIndex};
+
+    #[test]
+    fn indexes_yaml_positions() {
+        let index = YamlIndex::new("a:\n  b: [1, 'two']\nc/d: x\n").unwrap();
+
+        assert_eq!(index.positions.get(""), Some(&(1, 1)));
+        assert_eq!(index.positions.get("/a/b/1"), Some(&(2, 10)));
+        assert_eq!(index.positions.get("/c~1d"), Some(&(3, 6)));
+        assert_eq!(index.keys.get("/a/b"), Some(&(2, 3)));
+        assert_eq!(index.keys.get("/c~1d"), Some(&(3, 1)));
+    }
+
+    #[test]
+    fn finds_duplicate_keys() {
+        let index = YamlIndex::new("title: a\nauthor:\n  name: b\n  name: c\ntitle: d\n").unwrap();
+        let duplicates = index
+            .duplicates
+            .iter()
+            .map(|duplicate| (dotted(&duplicate.path), duplicate.first, duplicate.second))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            duplicates,
+            vec![
+                ("author.name".to_string(), (3, 3), (4, 3)),
+                ("title".to_string(), (1, 1), (5, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_resolved_keys() {
+        let index = YamlIndex::new("1: a\n'1': b\n!!str 1: c\ntrue: d\nTrue: e\n").unwrap();
+        let duplicates = index
+            .duplicates
+            .iter()
+            .map(|duplicate| (duplicate.first, duplicate.second))
+            .collect::<Vec<_>>();
+
+        assert_eq!(duplicates, vec![((2, 1), (3, 7)), ((4, 1), (5, 1))]);
+    }
+
+    #[test]
+    fn flags_anchored_entries() {
+        let index = YamlIndex::new("a: &x 1\nb:\n  c: [&y 2]\nb: 3\na: 4\n").unwrap();
+        let anchored = index
+            .duplicates
+            .iter()
+            .map(|duplicate| (duplicate.first_anchored, duplicate.second_anchored))
+            .collect::<Vec<_>>();
+
+        assert_eq!(anchored, vec![(true, false), (true, false)]);
+    }
+
+    #[test]
+    fn finds_block_scalar_lines() {
+        let index = YamlIndex::new("a: |\n  x\n  \ty\n\nb: >-\n  z\nc: 1\n").unwrap();
+
+        assert_eq!(index.block_scalars, vec![2..5, 6..7]);
+    }
+
+    #[test]
+    fn spans_duplicate_entries() {
+        let yaml = "title: é\nauthor: {name: a, !!str name: b}\n&t title: c\n";
+        let index = YamlIndex::new(yaml).unwrap();
+        let entries = index
+            .duplicates
+            .iter()
+            .map(|duplicate| {
+                (
+                    &yaml[duplicate.first_entry.clone()],
+                    &yaml[duplicate.second_entry.clone()],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("name: a, ", "!!str name: b"),
+                ("title: é\n", "&t title: c\n")
+            ]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::ops::Range;
+
+/// Where the front matter and the body of a Markdown document live, so tools
+/// can highlight, fold or rewrite precise regions of the document.
+///
+/// ```
+/// use serde::Deserialize;
+/// use yaml_front_matter::YamlFrontMatter;
+///
+/// #[derive(Deserialize)]
+/// struct Metadata {
+///     title: String,
+/// }
+///
+/// let markdown = "---\ntitle: 'Layout'\n---\n# Layout\n";
+/// let document = YamlFrontMatter::parse::<Metadata>(markdown).unwrap();
+/// let layout = document.layout.unwrap();
+///
+/// assert_eq!(layout.opening_fence().bytes(), 0..3);
+/// assert_eq!(layout.front_matter().lines(), 2..3);
+/// assert_eq!(layout.closing_fence().lines(), 3..4);
+/// assert_eq!(&markdown[layout.body().bytes()], "\n# Layout\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    opening_fence: Region,
+    front_matter: Region,
+    closing_fence: Region,
+    body: Region,
+    /// Whether the line ending of the closing fence was left out of the body
+    strips_fence_newline: bool,
+}
+
+/// A region of the Markdown document, as a byte range and a range of 1-based
+/// line numbers, both exclusive of their end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    bytes: Range<usize>,
+    lines: Range<usize>,
+}
+
+impl Layout {
+    /// Builds a `Layout` from the byte ranges of each region of `markdown`.
+    ///
+    /// The lines of the body begin on the line following the closing fence,
+    /// even if the body holds the line ending of the closing fence.
+    pub(crate) fn new(
+        markdown: &str,
+        opening_fence: Range<usize>,
+        front_matter: Range<usize>,
+        closing_fence: Range<usize>,
+        body: Range<usize>,
+    ) -> Layout {
+        let closing_fence = Region::new(markdown, closing_fence);
+        let body_line = closing_fence.lines.end.max(closing_fence.lines.start + 1);
+        let last_line = markdown.lines().count() + 1;
+        let strips_fence_newline = markdown[closing_fence.bytes.end..body.start].contains('\n');
+
+        Layout {
+            opening_fence: Region::new(markdown, opening_fence),
+            front_matter: Region::new(markdown, front_matter),
+            body: Region {
+                bytes: body,
+                lines: body_line..last_line.max(body_line),
+            },
+            closing_fence,
+            strips_fence_newline,
+        }
+    }
+
+    /// The line opening the front matter, without its line ending.
+    ///
+    /// Empty for bare JSON front matter, which has no fences.
+    pub fn opening_fence(&self) -> &Region {
+        &self.opening_fence
+    }
+
+    /// The front matter between the fences.
+    pub fn front_matter(&self) -> &Region {
+        &self.front_matter
+    }
+
+    /// The line closing the front matter, without its line ending.
+    ///
+    /// Empty for bare JSON front matter, which has no fences.
+    pub fn closing_fence(&self) -> &Region {
+        &self.closing_fence
+    }
+
+    /// The body of the Markdown, as found in `Document::content`.
+    pub fn body(&self) -> &Region {
+        &self.body
+    }
+
+    /// Whether the line ending of the closing fence is left out of the body,
+    /// as `Parser::strip_fence_newline` does.
+    pub(crate) fn strips_fence_newline(&self) -> bool {
+        self.strips_fence_newline
+    }
+}
+
+impl Region {
+    fn new(markdown: &str, bytes: Range<usize>) -> Region {
+        let start = line_at(markdown, bytes.start);
+        let end = match bytes.is_empty() {
+            true => start,
+            false => line_at(markdown, bytes.end - 1) + 1,
+        };
+
+        Region {
+            bytes,
+            lines: start..end,
+        }
+    }
+
+    /// The byte range of the region in the Markdown document.
+    pub fn bytes(&self) -> Range<usize> {
+        self.bytes.clone()
+    }
+
+    /// The 1-based line numbers of the region in the Markdown document.
+    pub fn lines(&self) -> Range<usize> {
+        self.lines.clone()
+    }
+}
+
+/// The 1-based line holding the byte at `offset`.
+fn line_at(markdown: &str, offset: usize) -> usize {
+    markdown.as_bytes()[..offset]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Parser;
+
+    #[test]
+    fn locates_fences_front_matter_and_body() {
+        let markdown = "\u{feff}---\r\ntitle: 'Layout'\r\ntags: []\r\n---\r\n\r\n# Layout\r\n";
+        let extracted = Parser::new().extract(markdown).unwrap().unwrap();
+        let layout = extracted.layout;
+
+        assert_eq!(&markdown[layout.opening_fence().bytes()], "---");
+        assert_eq!(layout.opening_fence().lines(), 1..2);
+        assert_eq!(
+            &markdown[layout.front_matter().bytes()],
+            "title: 'Layout'\r\ntags: []\r\n"
+        );
+        assert_eq!(layout.front_matter().lines(), 2..4);
+        assert_eq!(&markdown[layout.closing_fence().bytes()], "---");
+        assert_eq!(layout.closing_fence().lines(), 4..5);
+        assert_eq!(&markdown[layout.body().bytes()], "\r\n\r\n# Layout\r\n");
+        assert_eq!(layout.body().lines(), 5..7);
+    }
+
+    #[test]
+    fn locates_empty_front_matter_and_body() {
+        let markdown = "---\n---";
+        let extracted = Parser::new().extract(markdown).unwrap().unwrap();
+        let layout = extracted.layout;
+
+        assert_eq!(layout.front_matter().bytes(), 4..4);
+        assert_eq!(layout.front_matter().lines(), 2..2);
+        assert_eq!(layout.closing_fence().bytes(), 4..7);
+        assert_eq!(layout.body().bytes(), 7..7);
+        assert_eq!(layout.body().lines(), 3..3);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn locates_bare_json_front_matter() {
+        let markdown = "\n{\n  \"title\": \"Layout\"\n}\n# Layout\n";
+        let extracted = Parser::new().extract(markdown).unwrap().unwrap();
+        let layout = extracted.layout;
+
+        assert_eq!(layout.opening_fence().bytes(), 1..1);
+        assert_eq!(layout.front_matter().lines(), 2..5);
+        assert_eq!(layout.closing_fence().lines(), 4..4);
+        assert_eq!(&markdown[layout.body().bytes()], "\n# Layout\n");
+        assert_eq!(layout.body().lines(), 5..6);
+    }
+}
@@ -89,24 +89,72 @@
 //! assert_eq!(favorite_numbers, vec![3.14, 1970., 12345.]);
 //! ```
 //!
-use serde::de::DeserializeOwned;
-
-/// A `Document` represents the Markdown file provided as input to
-/// `YamlFrontMatter::parse` associated function.
-///
-/// The document holds two relevant fields:
-///
-/// - `metadata`: A generic type with the structure of the Markdown's
-/// front matter header.
-///
-/// - `content`: The body of the Markdown without the front matter header
-pub struct Document<T: DeserializeOwned> {
-    /// A generic type with the structure of the Markdown's
-    /// front matter header.
-    pub metadata: T,
-    /// The body of the Markdown without the front matter header
-    pub content: String,
-}
+//! ## Features
+//!
+//! - `checks`: Detects keys defined more than once in YAML front matter
+//!   through `Parser::duplicate_key_policy` and `Parser::duplicate_keys`, and
+//!   warns about unknown and deprecated keys and tab indentation.
+//! - `cli`: Builds the `yfm` command-line tool which prints the front matter,
+//!   the body or a front matter value of a document, lints the front matter
+//!   of many documents and sets, unsets or renames their keys in place.
+//! - `collection`: Parses every Markdown document under a directory matching
+//!   a glob pattern through `Collection`.
+//! - `json`: Parses JSON front matter, either as a bare object at the top of
+//!   the document or between `;;;` fences.
+//! - `parallel`: Parses the documents of a `Collection` in parallel.
+//! - `schema`: Validates front matter against a JSON Schema through `Schema`.
+//! - `tokio`: Reads documents from files and `AsyncBufRead` streams without
+//!   blocking the executor.
+//! - `toml`: Parses TOML front matter between `+++` fences.
+//!
+#[cfg(feature = "collection")]
+mod collection;
+mod document;
+mod duplicate;
+mod editor;
+mod error;
+mod format;
+mod head;
+mod header;
+#[cfg(feature = "checks")]
+mod index;
+mod layout;
+mod location;
+mod metadata;
+mod parsed;
+mod parser;
+#[cfg(feature = "schema")]
+mod schema;
+mod split;
+mod violation;
+mod warning;
+
+#[cfg(feature = "collection")]
+pub use collection::{Collected, Collection};
+pub use document::{Document, DocumentRef};
+pub use duplicate::{DuplicateKey, DuplicateKeyPolicy};
+pub use editor::Editor;
+pub use error::FrontMatterError;
+pub use format::Format;
+pub use head::Head;
+pub use header::Header;
+pub use layout::{Layout, Region};
+pub use location::Location;
+pub use metadata::Metadata;
+pub use parsed::Parsed;
+pub use parser::Parser;
+#[cfg(feature = "schema")]
+pub use schema::Schema;
+pub use split::Split;
+pub use violation::Violation;
+pub use warning::{Warning, WarningCode};
+
+use std::io::BufRead;
+use std::path::Path;
+
+use serde::de::{Deserialize, DeserializeOwned};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncBufRead;
 
 /// YAML Front Matter (YFM) is an optional section of valid YAML that is
 /// placed at the top of a page and is used for maintaining metadata for the
@@ -114,57 +162,301 @@ pub struct Document<T: DeserializeOwned> {
 pub struct YamlFrontMatter;
 
 impl YamlFrontMatter {
-    pub fn parse<T: DeserializeOwned>(
-        markdown: &str,
-    ) -> Result<Document<T>, Box<dyn std::error::Error>> {
-        let yaml = YamlFrontMatter::extract(markdown)?;
-        let metadata = serde_yaml::from_str::<T>(yaml.0.as_str())?;
+    /// Parses the front matter of the provided Markdown into `T`.
+    ///
+    /// The first `---` line found in the document opens the front matter,
+    /// use `Parser` to only recognize front matter at the start of the
+    /// document.
+    pub fn parse<T: DeserializeOwned>(markdown: &str) -> Result<Document<T>, FrontMatterError> {
+        let document = Parser::default().parse::<T>(markdown)?;
 
         Ok(Document {
-            metadata,
-            content: yaml.1,
+            metadata: document
+                .metadata
+                .ok_or(FrontMatterError::MissingOpeningFence)?,
+            content: document.content,
+            format: document.format,
+            layout: document.layout,
         })
     }
 
-    fn extract(markdown: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
-        let mut front_matter = String::default();
-        let mut sentinel = false;
-        let mut front_matter_lines = 0;
-        let lines = markdown.lines();
+    /// Reads the Markdown document at `path` and parses its front matter
+    /// into `T`.
+    ///
+    /// Errors name the file, which helps telling which document failed when
+    /// parsing many of them.
+    pub fn parse_file<T: DeserializeOwned, P: AsRef<Path>>(
+        path: P,
+    ) -> Result<Document<T>, FrontMatterError> {
+        parser::read_file(path.as_ref(), YamlFrontMatter::parse::<T>)
+    }
 
-        for line in lines.clone() {
-            front_matter_lines += 1;
+    /// Reads the Markdown document from `reader` and parses its front matter
+    /// into `T`.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::YamlFrontMatter;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata {
+    ///     title: String,
+    /// }
+    ///
+    /// let reader = "---\ntitle: 'Read'\n---\n".as_bytes();
+    /// let document = YamlFrontMatter::parse_reader::<Metadata, _>(reader).unwrap();
+    ///
+    /// assert_eq!(document.metadata.title, "Read");
+    /// ```
+    pub fn parse_reader<T: DeserializeOwned, R: BufRead>(
+        reader: R,
+    ) -> Result<Document<T>, FrontMatterError> {
+        YamlFrontMatter::parse::<T>(&parser::read_to_string(reader)?)
+    }
 
-            if line.trim() == "---" {
-                if sentinel {
-                    break;
-                }
+    /// Reads the Markdown document from `reader` up to the closing fence and
+    /// parses its front matter into `T`, without reading the body.
+    ///
+    /// ```
+    /// use std::io::{BufRead, Cursor};
+    ///
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::YamlFrontMatter;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata {
+    ///     title: String,
+    /// }
+    ///
+    /// let mut reader = Cursor::new("---\ntitle: 'Listed'\n---\n# Listed\n");
+    /// let head = YamlFrontMatter::read_head::<Metadata, _>(&mut reader).unwrap();
+    ///
+    /// assert_eq!(head.metadata.title, "Listed");
+    /// assert_eq!(head.body_offset, 23);
+    /// assert_eq!(reader.lines().next().unwrap().unwrap(), "# Listed");
+    /// ```
+    pub fn read_head<T: DeserializeOwned, R: BufRead>(
+        reader: R,
+    ) -> Result<Head<T>, FrontMatterError> {
+        let head = Parser::default().read_head::<T, R>(reader)?;
+
+        Ok(Head {
+            metadata: head.metadata.ok_or(FrontMatterError::MissingOpeningFence)?,
+            format: head.format,
+            body_offset: head.body_offset,
+        })
+    }
 
-                sentinel = true;
-                continue;
-            }
+    /// Reads the whole Markdown document from the asynchronous `reader` and
+    /// parses its front matter into `T`.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_async<T, R>(reader: R) -> Result<Document<T>, FrontMatterError>
+    where
+        T: DeserializeOwned,
+        R: AsyncBufRead + Unpin,
+    {
+        let document = Parser::default().parse_async::<T, R>(reader).await?;
 
-            if sentinel {
-                front_matter.push_str(line);
-                front_matter.push('\n');
-            }
-        }
+        Ok(Document {
+            metadata: document
+                .metadata
+                .ok_or(FrontMatterError::MissingOpeningFence)?,
+            content: document.content,
+            format: document.format,
+            layout: document.layout,
+        })
+    }
+
+    /// Reads the Markdown document at `path` without blocking and parses its
+    /// front matter into `T`.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_file_async<T: DeserializeOwned, P: AsRef<Path>>(
+        path: P,
+    ) -> Result<Document<T>, FrontMatterError> {
+        let path = path.as_ref();
+        let document = Parser::default().parse_file_async::<T, _>(path).await?;
+
+        Ok(Document {
+            metadata: document
+                .metadata
+                .ok_or_else(|| FrontMatterError::MissingOpeningFence.in_file(path.to_path_buf()))?,
+            content: document.content,
+            format: document.format,
+            layout: document.layout,
+        })
+    }
+
+    /// Reads the Markdown document from the asynchronous `reader` up to the
+    /// closing fence and parses its front matter into `T`, without reading
+    /// the body.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::YamlFrontMatter;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata {
+    ///     title: String,
+    /// }
+    ///
+    /// let reader = "---\ntitle: 'Awaited'\n---\n# Awaited\n".as_bytes();
+    /// let head = YamlFrontMatter::read_head_async::<Metadata, _>(reader).await.unwrap();
+    ///
+    /// assert_eq!(head.metadata.title, "Awaited");
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn read_head_async<T, R>(reader: R) -> Result<Head<T>, FrontMatterError>
+    where
+        T: DeserializeOwned,
+        R: AsyncBufRead + Unpin,
+    {
+        let head = Parser::default().read_head_async::<T, R>(reader).await?;
+
+        Ok(Head {
+            metadata: head.metadata.ok_or(FrontMatterError::MissingOpeningFence)?,
+            format: head.format,
+            body_offset: head.body_offset,
+        })
+    }
+
+    /// Parses the front matter of the provided Markdown into `T` without
+    /// copying the Markdown, so `T` may borrow from it.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::YamlFrontMatter;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata<'a> {
+    ///     title: &'a str,
+    /// }
+    ///
+    /// let markdown = "---\ntitle: Borrowed\n---\n# Borrowed\n";
+    /// let document = YamlFrontMatter::parse_ref::<Metadata>(markdown).unwrap();
+    ///
+    /// assert_eq!(document.metadata.title, "Borrowed");
+    /// assert_eq!(document.front_matter, "title: Borrowed\n");
+    /// assert_eq!(document.content, "\n# Borrowed\n");
+    /// ```
+    pub fn parse_ref<'a, T: Deserialize<'a>>(
+        markdown: &'a str,
+    ) -> Result<DocumentRef<'a, T>, FrontMatterError> {
+        let document = Parser::default().parse_ref::<T>(markdown)?;
+
+        Ok(DocumentRef {
+            metadata: document
+                .metadata
+                .ok_or(FrontMatterError::MissingOpeningFence)?,
+            front_matter: document.front_matter,
+            content: document.content,
+            format: document.format,
+            layout: document.layout,
+        })
+    }
+
+    /// Splits the provided Markdown into its raw front matter and its body,
+    /// following the same rules as `parse` without deserializing the front
+    /// matter.
+    ///
+    /// Returns `None` if the document has no front matter or if it is never
+    /// closed, use `Parser::split` to tell these apart.
+    ///
+    /// ```
+    /// use yaml_front_matter::YamlFrontMatter;
+    ///
+    /// let markdown = "---\ntitle: 'Raw'\n---\n# Raw\n";
+    /// let split = YamlFrontMatter::split(markdown).unwrap();
+    ///
+    /// assert_eq!(split.front_matter, "title: 'Raw'\n");
+    /// assert_eq!(split.body, "\n# Raw\n");
+    /// ```
+    pub fn split(markdown: &str) -> Option<Split<'_>> {
+        Parser::default().split(markdown).ok().flatten()
+    }
+
+    /// Parses the front matter of the provided Markdown into `T`, telling
+    /// apart documents without front matter, with an empty front matter and
+    /// with front matter data.
+    ///
+    /// Only a fence on the first non-blank line opens the front matter, so
+    /// thematic breaks in documents without front matter are left alone.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::{Header, YamlFrontMatter};
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Metadata {
+    ///     title: String,
+    /// }
+    ///
+    /// let document = YamlFrontMatter::parse_optional::<Metadata>("# No front matter").unwrap();
+    ///
+    /// assert_eq!(document.metadata, Header::Absent);
+    /// assert_eq!(document.content, "# No front matter");
+    /// ```
+    pub fn parse_optional<T: DeserializeOwned>(
+        markdown: &str,
+    ) -> Result<Document<Header<T>>, FrontMatterError> {
+        Parser::new()
+            .strict(true)
+            .allow_leading_blank_lines(true)
+            .parse_optional::<T>(markdown)
+    }
+}
+
+/// Front matter in any of the formats enabled by cargo features.
+pub struct FrontMatter;
+
+impl FrontMatter {
+    /// Parses the front matter of the provided Markdown into `T`, detecting
+    /// its format from the opening fence, which must be the first line of
+    /// the document other than blank lines.
+    ///
+    /// The opening fence is either the format's own fence (`---` for YAML,
+    /// `+++` for TOML, `;;;` for JSON), the `{` of a bare JSON object or
+    /// `---` followed by a language hint such as `---toml`, as supported by
+    /// gray-matter. The detected format is available as `Document::format`.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use yaml_front_matter::{Format, FrontMatter};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Metadata {
+    ///     title: String,
+    /// }
+    ///
+    /// let document = FrontMatter::parse_any::<Metadata>("---yaml\ntitle: 'Hinted'\n---\n").unwrap();
+    ///
+    /// assert_eq!(document.metadata.title, "Hinted");
+    /// assert_eq!(document.format, Some(Format::Yaml));
+    /// ```
+    pub fn parse_any<T: DeserializeOwned>(markdown: &str) -> Result<Document<T>, FrontMatterError> {
+        let document = Parser::new()
+            .strict(true)
+            .allow_leading_blank_lines(true)
+            .parse::<T>(markdown)?;
 
-        Ok((
-            front_matter,
-            lines
-                .skip(front_matter_lines)
-                .collect::<Vec<&str>>()
-                .join("\n"),
-        ))
+        Ok(Document {
+            metadata: document
+                .metadata
+                .ok_or(FrontMatterError::MissingOpeningFence)?,
+            content: document.content,
+            format: document.format,
+            layout: document.layout,
+        })
     }
 }
 
 #[cfg(test)]
 mod test {
-    use serde::{Deserialize, __private::doc};
+    use serde::Deserialize;
 
-    const MARKDOWN: &'static str = r#"
+    const MARKDOWN: &str = r#"
 ---
 title: "Installing The Rust Programming Language on Windows"
 description: "A tutorial on installing the Rust Programming Language on Windows."
@@ -198,13 +490,14 @@ After having Windows up and running, I'm also installing Rust on Windows and I'm
 it for future references.
 "#;
 
-    const FRONT_MATTER: &'static str = r#"title: "Installing The Rust Programming Language on Windows"
+    const FRONT_MATTER: &str = r#"title: "Installing The Rust Programming Language on Windows"
 description: "A tutorial on installing the Rust Programming Language on Windows."
 categories: [rust, tutorial, windows, install]
 date: 2021-09-13T03:48:00
 "#;
 
-    const CONTENT: &'static str = r#"
+    const CONTENT: &str = r#"
+
 # Installing The Rust Programming Language on Windows
 
 ## Motivation
@@ -228,7 +521,8 @@ details, I decided to install Windows in my PC as a dual-boot option along with
 This will give me first-class access to the popular Win32 API, which I'm using through [windows-rs](https://github.com/microsoft/windows-rs) crate.
 
 After having Windows up and running, I'm also installing Rust on Windows and I'm documenting
-it for future references."#;
+it for future references.
+"#;
 
     #[derive(Deserialize)]
     struct Metadata {
@@ -240,16 +534,25 @@ it for future references."#;
 
     #[test]
     fn retrieve_markdown_front_matter() {
-        let (front_matter, _) = super::YamlFrontMatter::extract(MARKDOWN).unwrap();
+        let split = super::YamlFrontMatter::split(MARKDOWN).unwrap();
 
-        assert_eq!(front_matter, FRONT_MATTER);
+        assert_eq!(split.front_matter, FRONT_MATTER);
     }
 
     #[test]
     fn retrieve_markdown_content() {
-        let (_, content) = super::YamlFrontMatter::extract(MARKDOWN).unwrap();
+        let split = super::YamlFrontMatter::split(MARKDOWN).unwrap();
+
+        assert_eq!(split.body, CONTENT);
+    }
 
-        assert_eq!(content, CONTENT);
+    #[test]
+    fn splits_only_documents_with_front_matter() {
+        assert!(super::YamlFrontMatter::split("# No front matter here").is_none());
+        assert!(super::YamlFrontMatter::split("---\ntitle: 'Unterminated'\n").is_none());
+        assert!(super::Parser::default()
+            .split("---\ntitle: 'Unterminated'\n")
+            .is_err());
     }
 
     #[test]
@@ -271,4 +574,162 @@ it for future references."#;
         );
         assert_eq!(metadata.date, "2021-09-13T03:48:00");
     }
+
+    #[test]
+    fn fails_on_missing_opening_fence() {
+        let result = super::YamlFrontMatter::parse::<Metadata>("# No front matter here");
+
+        assert!(matches!(
+            result,
+            Err(super::FrontMatterError::MissingOpeningFence)
+        ));
+    }
+
+    #[test]
+    fn fails_on_missing_closing_fence() {
+        let result = super::YamlFrontMatter::parse::<Metadata>("---\ntitle: 'Unterminated'\n");
+
+        assert!(matches!(
+            result,
+            Err(super::FrontMatterError::MissingClosingFence { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn tells_apart_syntax_and_deserialize_errors() {
+        let syntax = super::YamlFrontMatter::parse::<Metadata>("---\ntitle: [\n---\n");
+        let deserialize = super::YamlFrontMatter::parse::<Metadata>("---\ntitle: 1\n---\n");
+
+        assert!(matches!(
+            syntax,
+            Err(super::FrontMatterError::Syntax { .. })
+        ));
+        assert!(matches!(
+            deserialize,
+            Err(super::FrontMatterError::Deserialize { .. })
+        ));
+    }
+
+    #[test]
+    fn reports_cause_of_syntax_errors() {
+        use std::error::Error;
+
+        let err = super::YamlFrontMatter::parse::<Metadata>("---\ntitle: [\n---\n")
+            .err()
+            .unwrap();
+        let source = err.source().unwrap().to_string();
+
+        assert!(!source.contains("invalid type"), "{}", source);
+        assert_eq!(err.location().unwrap().line(), 3);
+        assert_eq!(
+            err.message().unwrap(),
+            "did not find expected node content, while parsing a flow node"
+        );
+    }
+
+    #[test]
+    fn parses_markdown_file() {
+        let path = std::env::temp_dir().join(format!("yfm-{}.md", std::process::id()));
+
+        std::fs::write(&path, MARKDOWN).unwrap();
+
+        let document = super::YamlFrontMatter::parse_file::<Metadata, _>(&path);
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(document.unwrap().content, CONTENT);
+    }
+
+    #[test]
+    fn names_file_in_errors() {
+        let result = super::YamlFrontMatter::parse_file::<Metadata, _>("missing/post.md");
+        let err = result.err().unwrap();
+
+        assert!(err.to_string().starts_with("missing/post.md: "));
+        assert!(matches!(
+            err,
+            super::FrontMatterError::File { source, .. }
+                if matches!(*source, super::FrontMatterError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn maps_error_location_to_markdown() {
+        let markdown = "\n---\ntitle: Located: here\n---\n";
+        let err = super::YamlFrontMatter::parse::<Metadata>(markdown)
+            .err()
+            .unwrap();
+        let location = err.location().unwrap();
+
+        assert_eq!(location.line(), 3);
+        assert_eq!(location.column(), 15);
+        assert_eq!(location.span(), 19..20);
+        assert_eq!(
+            location.snippet(),
+            "3 | title: Located: here\n  |               ^"
+        );
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Title {
+        title: String,
+    }
+
+    #[test]
+    fn parse_any_detects_yaml() {
+        let document = super::FrontMatter::parse_any::<Title>("\n---\ntitle: YAML\n---\n").unwrap();
+
+        assert_eq!(document.metadata.title, "YAML");
+        assert_eq!(document.format, Some(super::Format::Yaml));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn parse_any_detects_toml() {
+        let fenced = super::FrontMatter::parse_any::<Title>("+++\ntitle = 'TOML'\n+++\n").unwrap();
+        let hinted =
+            super::FrontMatter::parse_any::<Title>("---toml\ntitle = 'TOML'\n---\n").unwrap();
+
+        assert_eq!(fenced.format, Some(super::Format::Toml));
+        assert_eq!(hinted.metadata.title, "TOML");
+        assert_eq!(hinted.format, Some(super::Format::Toml));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parse_any_detects_json() {
+        let bare = super::FrontMatter::parse_any::<Title>("{ \"title\": \"JSON\" }\n").unwrap();
+        let hinted =
+            super::FrontMatter::parse_any::<Title>("---json\n{ \"title\": \"JSON\" }\n---\n")
+                .unwrap();
+
+        assert_eq!(bare.format, Some(super::Format::Json));
+        assert_eq!(hinted.metadata.title, "JSON");
+        assert_eq!(hinted.format, Some(super::Format::Json));
+    }
+
+    #[test]
+    fn parse_optional_ignores_thematic_breaks() {
+        for markdown in ["# Title\n\n---\n\nProse\n", "# Title\n---\ntitle: x\n---\n"] {
+            let document = super::YamlFrontMatter::parse_optional::<Title>(markdown).unwrap();
+
+            assert!(document.metadata.is_absent());
+            assert_eq!(document.content, markdown);
+        }
+
+        let document =
+            super::YamlFrontMatter::parse_optional::<Title>("\n---\ntitle: YAML\n---\n").unwrap();
+
+        assert_eq!(document.metadata.into_option().unwrap().title, "YAML");
+    }
+
+    #[test]
+    fn parse_any_requires_fence_at_start() {
+        let result = super::FrontMatter::parse_any::<Title>("# Title\n---\ntitle: YAML\n---\n");
+
+        assert!(matches!(
+            result,
+            Err(super::FrontMatterError::MissingOpeningFence)
+        ));
+    }
 }
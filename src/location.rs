@@ -0,0 +1,154 @@
+use std::fmt;
+use std::ops::Range;
+
+/// A position in the original Markdown document, used to point authors at
+/// the offending token of a malformed front matter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    line: usize,
+    column: usize,
+    span: Range<usize>,
+    source_line: String,
+    line_start: usize,
+}
+
+impl Location {
+    /// Builds a `Location` from a 1-based `line` and `column` relative to the
+    /// Markdown document, spanning the token found at that position.
+    ///
+    /// Returns `None` if the document has no such line.
+    pub(crate) fn new(markdown: &str, line: usize, column: usize) -> Option<Location> {
+        let mut line_start = 0;
+
+        for (index, text) in markdown.split_inclusive('\n').enumerate() {
+            if index + 1 < line {
+                line_start += text.len();
+                continue;
+            }
+
+            let source_line = text.trim_end_matches(&['\r', '\n'][..]);
+            let token_start = source_line
+                .char_indices()
+                .nth(column.saturating_sub(1))
+                .map_or(source_line.len(), |(offset, _)| offset);
+            let token = &source_line[token_start..];
+            let token_len = match token.find(char::is_whitespace) {
+                Some(0) => token.chars().next().map_or(0, char::len_utf8),
+                Some(len) => len,
+                None => token.len(),
+            };
+            let start = line_start + token_start;
+
+            return Some(Location {
+                line,
+                column,
+                span: start..start + token_len,
+                source_line: source_line.to_string(),
+                line_start,
+            });
+        }
+
+        None
+    }
+
+    /// Builds a `Location` for the byte at `offset` in the Markdown document.
+    pub(crate) fn at(markdown: &str, offset: usize) -> Option<Location> {
+        let line_start = markdown[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line = markdown[..offset].matches('\n').count() + 1;
+        let column = markdown[line_start..offset].chars().count() + 1;
+
+        Location::new(markdown, line, column)
+    }
+
+    /// Maps a 1-based `line` and `column` relative to the front matter back
+    /// to the Markdown document.
+    ///
+    /// `first_line` is the 0-based index of the line in `markdown` where the
+    /// front matter payload begins, and `first_column` the number of
+    /// characters preceding the payload on that line.
+    pub(crate) fn in_front_matter(
+        markdown: &str,
+        (first_line, first_column): (usize, usize),
+        line: usize,
+        column: usize,
+    ) -> Option<Location> {
+        match line {
+            1 => Location::new(markdown, first_line + line, first_column + column),
+            _ => Location::new(markdown, first_line + line, column),
+        }
+    }
+
+    /// The 1-based line in the Markdown document.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column, counted in characters, in the Markdown document.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte range of the offending token in the Markdown document.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Renders the offending line with carets pointing at the token.
+    ///
+    /// ```text
+    /// 3 | title: [
+    ///   |        ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string();
+        let start = self.span.start - self.line_start;
+        let end = self.span.end - self.line_start;
+        let indent = self.source_line[..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let carets = "^".repeat(self.source_line[start..end].chars().count().max(1));
+
+        format!(
+            "{} | {}\n{} | {}{}",
+            gutter,
+            self.source_line,
+            " ".repeat(gutter.len()),
+            indent,
+            carets
+        )
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Location;
+
+    const MARKDOWN: &str = "\n---\ntitle: [1]\n---\n";
+
+    #[test]
+    fn spans_token_at_position() {
+        let location = Location::new(MARKDOWN, 3, 8).unwrap();
+
+        assert_eq!(location.span(), 12..15);
+        assert_eq!(&MARKDOWN[location.span()], "[1]");
+    }
+
+    #[test]
+    fn renders_snippet_with_caret() {
+        let location = Location::new(MARKDOWN, 3, 8).unwrap();
+
+        assert_eq!(location.snippet(), "3 | title: [1]\n  |        ^^^");
+    }
+
+    #[test]
+    fn returns_none_for_missing_line() {
+        assert!(Location::new(MARKDOWN, 10, 1).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::{Format, FrontMatterError};
+
+/// Front matter of an unknown structure, for consumers which walk arbitrary
+/// keys instead of deserializing into a known `struct`.
+///
+/// Values are looked up by a dotted path of mapping keys and sequence
+/// indexes, such as `author.name` or `tags.0`.
+///
+/// ```
+/// use yaml_front_matter::{Metadata, YamlFrontMatter};
+///
+/// let markdown = "---\ntitle: 'Untyped'\nauthor:\n  name: Esteban\ntags: [rust, yaml]\n---\n";
+/// let document = YamlFrontMatter::parse::<Metadata>(markdown).unwrap();
+/// let metadata = document.metadata;
+///
+/// assert_eq!(metadata.get_str("author.name"), Some("Esteban"));
+/// assert_eq!(metadata.get_str("tags.1"), Some("yaml"));
+/// assert_eq!(metadata.get_array("tags").map(|tags| tags.len()), Some(2));
+/// assert_eq!(metadata.get_as::<String>("title").unwrap(), Some("Untyped".to_string()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Metadata(Value);
+
+impl Metadata {
+    /// Returns the value at the dotted `path`, `None` if there is no such
+    /// value.
+    ///
+    /// A key of the path missing from a mapping is looked up again as the
+    /// YAML scalar it resolves to, so `2021` finds a `2021:` number key.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        path.split('.').try_fold(&self.0, |value, key| {
+            let value = match value {
+                Value::Tagged(tagged) => &tagged.value,
+                value => value,
+            };
+
+            match value {
+                Value::Sequence(items) => items.get(key.parse::<usize>().ok()?),
+                Value::Mapping(entries) => entries
+                    .get(key)
+                    .or_else(|| entries.get(serde_yaml::from_str::<Value>(key).ok()?)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the string at the dotted `path`, `None` if there is no such
+    /// value or it isn't a string.
+    pub fn get_str(&self, path: &str) -> Option<&str> {
+        self.get(path).and_then(Value::as_str)
+    }
+
+    /// Returns the sequence at the dotted `path`, `None` if there is no such
+    /// value or it isn't a sequence.
+    pub fn get_array(&self, path: &str) -> Option<&[Value]> {
+        self.get(path)
+            .and_then(Value::as_sequence)
+            .map(Vec::as_slice)
+    }
+
+    /// Deserializes the value at the dotted `path` into `T`, `None` if there
+    /// is no such value.
+    pub fn get_as<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, FrontMatterError> {
+        let value = match self.get(path) {
+            Some(value) => value.clone(),
+            None => return Ok(None),
+        };
+
+        serde_yaml::from_value(value)
+            .map(Some)
+            .map_err(|err| FrontMatterError::Deserialize {
+                format: Format::Yaml,
+                message: err.to_string(),
+                source: Box::new(err),
+                location: None,
+            })
+    }
+
+    /// The whole front matter as a YAML value.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Consumes the `Metadata` returning the front matter as a YAML value.
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+}
+
+impl From<Value> for Metadata {
+    fn from(value: Value) -> Self {
+        Metadata(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Metadata;
+    use crate::{FrontMatterError, YamlFrontMatter};
+
+    const MARKDOWN: &str = r#"---
+title: "Untyped"
+author:
+  name: Esteban
+  links:
+    - https://github.com/EstebanBorai
+draft: false
+---
+"#;
+
+    #[test]
+    fn walks_dotted_paths() {
+        let metadata = YamlFrontMatter::parse::<Metadata>(MARKDOWN)
+            .unwrap()
+            .metadata;
+
+        assert_eq!(metadata.get_str("title"), Some("Untyped"));
+        assert_eq!(
+            metadata.get_str("author.links.0"),
+            Some("https://github.com/EstebanBorai")
+        );
+        assert!(metadata.get("author.links.1").is_none());
+        assert!(metadata.get("author.name.first").is_none());
+        assert!(metadata.get_str("draft").is_none());
+        assert!(metadata.get_array("author.links").is_some());
+    }
+
+    #[test]
+    fn walks_non_string_keys() {
+        let metadata =
+            YamlFrontMatter::parse::<Metadata>("---\n2021: yes\n'2022': no\ntrue: {1: a}\n---\n")
+                .unwrap()
+                .metadata;
+
+        assert_eq!(metadata.get_str("2021"), Some("yes"));
+        assert_eq!(metadata.get_str("2022"), Some("no"));
+        assert_eq!(metadata.get_str("true.1"), Some("a"));
+    }
+
+    #[test]
+    fn deserializes_value_at_path() {
+        let metadata = YamlFrontMatter::parse::<Metadata>(MARKDOWN)
+            .unwrap()
+            .metadata;
+
+        assert_eq!(metadata.get_as::<bool>("draft").unwrap(), Some(false));
+        assert_eq!(metadata.get_as::<bool>("missing").unwrap(), None);
+        assert!(matches!(
+            metadata.get_as::<bool>("title"),
+            Err(FrontMatterError::Deserialize { .. })
+        ));
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{Document, Warning};
+
+/// A parsed Markdown document along with the warnings about its front
+/// matter.
+///
+/// Returned by `Parser::parse_with_warnings`, which suits builds surfacing
+/// issues with the front matter without failing.
+pub struct Parsed<T> {
+    /// The parsed Markdown document.
+    pub document: Document<T>,
+    /// The issues found in the front matter, sorted by their location.
+    pub warnings: Vec<Warning>,
+}
@@ -0,0 +1,1613 @@
+use std::fs;
+use std::io::BufRead;
+use std::ops::Range;
+use std::path::Path;
+
+#[cfg(feature = "schema")]
+use crate::Schema;
+use serde::de::{Deserialize, DeserializeOwned};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+#[cfg(feature = "json")]
+use crate::format::BackendError;
+use crate::format::BackendErrorKind;
+#[cfg(feature = "checks")]
+use crate::index::{dotted, DuplicateEntry, YamlIndex};
+use crate::warning::Checks;
+#[cfg(feature = "checks")]
+use crate::DuplicateKey;
+use crate::{
+    Document, DocumentRef, DuplicateKeyPolicy, Format, FrontMatterError, Head, Header, Layout,
+    Location, Parsed, Split, Warning, WarningCode,
+};
+
+/// The byte order mark some editors place at the start of a file
+const BOM: char = '\u{feff}';
+
+/// A configurable front matter parser.
+///
+/// By default the first `---` line found anywhere in the document opens the
+/// front matter, which is the behavior of `YamlFrontMatter::parse`. Enabling
+/// `strict` mode only recognizes the front matter when the opening fence is
+/// the first line of the document, so Markdown files without front matter
+/// which contain a thematic break (`---`) are not mistaken for YAML.
+///
+/// ```
+/// use serde::Deserialize;
+/// use yaml_front_matter::Parser;
+///
+/// #[derive(Deserialize)]
+/// struct Metadata {
+///     title: String,
+/// }
+///
+/// let parser = Parser::new().strict(true);
+/// let document = parser
+///     .parse::<Metadata>("# No front matter\n\n---\n\ntitle: 'Not YAML'\n---\n")
+///     .unwrap();
+///
+/// assert!(document.metadata.is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Parser {
+    strict: bool,
+    allow_bom: bool,
+    allow_leading_blank_lines: bool,
+    allow_unterminated: bool,
+    strip_fence_newline: bool,
+    duplicate_key_policy: DuplicateKeyPolicy,
+    checks: Checks,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser {
+            strict: false,
+            allow_bom: true,
+            allow_leading_blank_lines: false,
+            allow_unterminated: false,
+            strip_fence_newline: false,
+            duplicate_key_policy: DuplicateKeyPolicy::Deserializer,
+            checks: Checks::default(),
+        }
+    }
+}
+
+impl Parser {
+    /// Creates a `Parser` with the default settings.
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// When `true`, the opening fence must be the first line of the document.
+    ///
+    /// Defaults to `false`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// When `true`, a byte order mark before the opening fence is ignored.
+    ///
+    /// Defaults to `true`.
+    pub fn allow_bom(mut self, allow_bom: bool) -> Self {
+        self.allow_bom = allow_bom;
+        self
+    }
+
+    /// When `true`, blank lines before the opening fence are ignored in
+    /// `strict` mode.
+    ///
+    /// Defaults to `false`.
+    pub fn allow_leading_blank_lines(mut self, allow_leading_blank_lines: bool) -> Self {
+        self.allow_leading_blank_lines = allow_leading_blank_lines;
+        self
+    }
+
+    /// When `true`, a front matter which is never closed is not an error and
+    /// the document is treated as having no front matter.
+    ///
+    /// Defaults to `false`.
+    pub fn allow_unterminated(mut self, allow_unterminated: bool) -> Self {
+        self.allow_unterminated = allow_unterminated;
+        self
+    }
+
+    /// When `true`, the line ending right after the closing fence is not
+    /// part of the body. Otherwise the body is everything following the
+    /// closing fence, byte for byte.
+    ///
+    /// Defaults to `false`.
+    pub fn strip_fence_newline(mut self, strip_fence_newline: bool) -> Self {
+        self.strip_fence_newline = strip_fence_newline;
+        self
+    }
+
+    /// What to do with keys defined more than once in the same mapping of a
+    /// YAML front matter.
+    ///
+    /// Defaults to `DuplicateKeyPolicy::Deserializer`.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use yaml_front_matter::{DuplicateKeyPolicy, Parser};
+    ///
+    /// let markdown = "---\ntitle: 'First'\ntitle: 'Second'\n---\n";
+    /// let document = Parser::new()
+    ///     .duplicate_key_policy(DuplicateKeyPolicy::KeepFirst)
+    ///     .parse::<HashMap<String, String>>(markdown)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(document.metadata.unwrap()["title"], "First");
+    /// ```
+    #[cfg(feature = "checks")]
+    pub fn duplicate_key_policy(mut self, duplicate_key_policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_key_policy = duplicate_key_policy;
+        self
+    }
+
+    /// Warns about top-level keys of a YAML front matter other than `keys`
+    /// with `WarningCode::UnknownKey`.
+    ///
+    /// Defaults to any key being known.
+    ///
+    /// ```
+    /// use yaml_front_matter::{Parser, WarningCode};
+    ///
+    /// let markdown = "---\ntitle: 'Hello'\nlayout: 'post'\n---\n# Hello\n";
+    /// let parsed = Parser::new()
+    ///     .known_keys(["title", "tags"])
+    ///     .parse_with_warnings::<serde_yaml::Value>(markdown)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(parsed.warnings[0].code(), WarningCode::UnknownKey);
+    /// assert_eq!(parsed.warnings[0].location().unwrap().line(), 3);
+    /// ```
+    #[cfg(feature = "checks")]
+    pub fn known_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.checks.known_keys = Some(keys.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Warns about the key at the dotted path `key` of a YAML front matter,
+    /// such as `author.email`, with `WarningCode::DeprecatedKey`, telling
+    /// authors what to do instead with `note`.
+    #[cfg(feature = "checks")]
+    pub fn deprecated_key(mut self, key: &str, note: &str) -> Self {
+        self.checks
+            .deprecated_keys
+            .push((key.to_string(), note.to_string()));
+        self
+    }
+
+    /// Fails with `FrontMatterError::Warning` on warnings with `code`
+    /// rather than reporting them, whichever method parses the document.
+    pub fn promote_warning(mut self, code: WarningCode) -> Self {
+        self.checks.promoted.push(code);
+        self
+    }
+
+    /// Parses the front matter of the provided Markdown into `T`.
+    ///
+    /// Documents without front matter are parsed into a `Document` with
+    /// `None` as `metadata` and the whole Markdown as `content`.
+    pub fn parse<T: DeserializeOwned>(
+        &self,
+        markdown: &str,
+    ) -> Result<Document<Option<T>>, FrontMatterError> {
+        self.parse_with_warnings::<T>(markdown)
+            .map(|parsed| parsed.document)
+    }
+
+    /// Parses the front matter of the provided Markdown into `T`, along
+    /// with the warnings about issues which don't prevent parsing it.
+    ///
+    /// Warnings whose code is promoted through `promote_warning` fail with
+    /// `FrontMatterError::Warning` instead.
+    ///
+    /// ```
+    /// use yaml_front_matter::{Parser, WarningCode};
+    ///
+    /// let markdown = "---\ntitle: 'Hello'\n--- \n# Hello\n";
+    /// let parsed = Parser::new()
+    ///     .parse_with_warnings::<serde_yaml::Value>(markdown)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(parsed.warnings[0].code(), WarningCode::FenceWhitespace);
+    /// assert_eq!(parsed.warnings[0].location().unwrap().line(), 3);
+    /// ```
+    pub fn parse_with_warnings<T: DeserializeOwned>(
+        &self,
+        markdown: &str,
+    ) -> Result<Parsed<Option<T>>, FrontMatterError> {
+        let extracted = match self.extract(markdown)? {
+            Some(extracted) => extracted,
+            None => {
+                return Ok(Parsed {
+                    document: Document {
+                        metadata: None,
+                        content: markdown.to_string(),
+                        format: None,
+                        layout: None,
+                    },
+                    warnings: Vec::new(),
+                })
+            }
+        };
+        let (metadata, warnings) = self.deserialize_checked::<T>(markdown, &extracted)?;
+
+        Ok(Parsed {
+            document: Document {
+                metadata: Some(metadata),
+                content: extracted.body.to_string(),
+                format: Some(extracted.format),
+                layout: Some(extracted.layout),
+            },
+            warnings,
+        })
+    }
+
+    /// Reads the Markdown document at `path` and parses its front matter
+    /// into `T`, attaching the path to any error.
+    pub fn parse_file<T: DeserializeOwned, P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Document<Option<T>>, FrontMatterError> {
+        read_file(path.as_ref(), |markdown| self.parse::<T>(markdown))
+    }
+
+    /// Reads the Markdown document at `path` and parses its front matter
+    /// into `T` along with its warnings, attaching the path to any error.
+    pub fn parse_file_with_warnings<T: DeserializeOwned, P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Parsed<Option<T>>, FrontMatterError> {
+        read_file(path.as_ref(), |markdown| {
+            self.parse_with_warnings::<T>(markdown)
+        })
+    }
+
+    /// Reads the Markdown document from `reader` and parses its front matter
+    /// into `T`.
+    pub fn parse_reader<T: DeserializeOwned, R: BufRead>(
+        &self,
+        reader: R,
+    ) -> Result<Document<Option<T>>, FrontMatterError> {
+        self.parse::<T>(&read_to_string(reader)?)
+    }
+
+    /// Reads the Markdown document from `reader` line by line up to the
+    /// closing fence and parses its front matter into `T`, without reading
+    /// the body.
+    ///
+    /// The reader is left at the start of the line following the closing
+    /// fence, so the body can still be read from it when passing
+    /// `&mut reader`. Documents without front matter are read to the end,
+    /// unless `strict` mode tells them apart from their first lines.
+    pub fn read_head<T: DeserializeOwned, R: BufRead>(
+        &self,
+        mut reader: R,
+    ) -> Result<Head<Option<T>>, FrontMatterError> {
+        let mut scanner = HeadScanner::new(self);
+
+        loop {
+            let start = scanner.head.len();
+
+            if reader.read_line(&mut scanner.head)? == 0 || scanner.is_complete(start)? {
+                break;
+            }
+        }
+
+        self.head::<T>(&scanner.head)
+    }
+
+    /// Reads the Markdown document from the asynchronous `reader` line by
+    /// line up to the closing fence and parses its front matter into `T`,
+    /// without reading the body, as `read_head` does.
+    #[cfg(feature = "tokio")]
+    pub async fn read_head_async<T, R>(
+        &self,
+        mut reader: R,
+    ) -> Result<Head<Option<T>>, FrontMatterError>
+    where
+        T: DeserializeOwned,
+        R: AsyncBufRead + Unpin,
+    {
+        let mut scanner = HeadScanner::new(self);
+
+        loop {
+            let start = scanner.head.len();
+
+            if reader.read_line(&mut scanner.head).await? == 0 || scanner.is_complete(start)? {
+                break;
+            }
+        }
+
+        self.head::<T>(&scanner.head)
+    }
+
+    /// Reads the whole Markdown document from the asynchronous `reader` and
+    /// parses its front matter into `T`.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_async<T, R>(
+        &self,
+        mut reader: R,
+    ) -> Result<Document<Option<T>>, FrontMatterError>
+    where
+        T: DeserializeOwned,
+        R: AsyncBufRead + Unpin,
+    {
+        let mut markdown = String::new();
+
+        reader.read_to_string(&mut markdown).await?;
+
+        self.parse::<T>(&markdown)
+    }
+
+    /// Reads the Markdown document at `path` without blocking and parses its
+    /// front matter into `T`, attaching the path to any error.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_file_async<T: DeserializeOwned, P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Document<Option<T>>, FrontMatterError> {
+        let path = path.as_ref();
+
+        tokio::fs::read_to_string(path)
+            .await
+            .map_err(FrontMatterError::from)
+            .and_then(|markdown| self.parse::<T>(&markdown))
+            .map_err(|err| err.in_file(path.to_path_buf()))
+    }
+
+    /// Parses the front matter of a document read by `HeadScanner`.
+    fn head<T: DeserializeOwned>(&self, head: &str) -> Result<Head<Option<T>>, FrontMatterError> {
+        let extracted = match self.extract(head)? {
+            Some(extracted) => extracted,
+            None => {
+                return Ok(Head {
+                    metadata: None,
+                    format: None,
+                    body_offset: 0,
+                })
+            }
+        };
+        let (metadata, _) = self.deserialize_checked::<T>(head, &extracted)?;
+
+        Ok(Head {
+            metadata: Some(metadata),
+            format: Some(extracted.format),
+            body_offset: extracted.layout.body().bytes().start,
+        })
+    }
+
+    /// Validates the front matter of the provided Markdown against `schema`
+    /// before deserializing it, reporting every violation of the schema.
+    ///
+    /// Documents without front matter have nothing to validate.
+    #[cfg(feature = "schema")]
+    pub fn validate(&self, markdown: &str, schema: &Schema) -> Result<(), FrontMatterError> {
+        let extracted = match self.extract(markdown)? {
+            Some(extracted) => extracted,
+            None => return Ok(()),
+        };
+
+        match self
+            .check(self.duplicate_key_policy, markdown, &extracted)?
+            .0
+        {
+            Some(front_matter) => schema.validate_front_matter(
+                markdown,
+                &Extracted {
+                    front_matter: &front_matter,
+                    ..extracted
+                },
+            ),
+            None => schema.validate_front_matter(markdown, &extracted),
+        }
+    }
+
+    /// Parses the front matter of the provided Markdown into `T` once it is
+    /// validated against `schema`.
+    #[cfg(feature = "schema")]
+    pub fn parse_validated<T: DeserializeOwned>(
+        &self,
+        markdown: &str,
+        schema: &Schema,
+    ) -> Result<Document<Option<T>>, FrontMatterError> {
+        self.validate(markdown, schema)?;
+        self.parse::<T>(markdown)
+    }
+
+    /// Parses the front matter of the provided Markdown into `T`, telling
+    /// apart documents without front matter, with an empty front matter and
+    /// with front matter data.
+    ///
+    /// Documents without front matter are parsed into a `Document` with
+    /// the whole Markdown as `content`.
+    pub fn parse_optional<T: DeserializeOwned>(
+        &self,
+        markdown: &str,
+    ) -> Result<Document<Header<T>>, FrontMatterError> {
+        let extracted = match self.extract(markdown)? {
+            Some(extracted) => extracted,
+            None => {
+                return Ok(Document {
+                    metadata: Header::Absent,
+                    content: markdown.to_string(),
+                    format: None,
+                    layout: None,
+                })
+            }
+        };
+
+        if is_blank(extracted.front_matter) {
+            return Ok(Document {
+                metadata: Header::Empty,
+                content: extracted.body.to_string(),
+                format: Some(extracted.format),
+                layout: Some(extracted.layout),
+            });
+        }
+
+        let (metadata, _) = self.deserialize_checked::<T>(markdown, &extracted)?;
+
+        Ok(Document {
+            metadata: Header::Present(metadata),
+            content: extracted.body.to_string(),
+            format: Some(extracted.format),
+            layout: Some(extracted.layout),
+        })
+    }
+
+    /// Parses the front matter of the provided Markdown into `T` without
+    /// copying the Markdown, so `T` may borrow from it.
+    ///
+    /// Documents without front matter are parsed into a `DocumentRef` with
+    /// `None` as `metadata` and the whole Markdown as `content`. As `T`
+    /// borrows from the front matter as written, keys defined more than once
+    /// are an error under any `duplicate_key_policy` but
+    /// `DuplicateKeyPolicy::Deserializer`.
+    pub fn parse_ref<'a, T: Deserialize<'a>>(
+        &self,
+        markdown: &'a str,
+    ) -> Result<DocumentRef<'a, Option<T>>, FrontMatterError> {
+        let extracted = match self.extract(markdown)? {
+            Some(extracted) => extracted,
+            None => {
+                return Ok(DocumentRef {
+                    metadata: None,
+                    front_matter: "",
+                    content: markdown,
+                    format: None,
+                    layout: None,
+                })
+            }
+        };
+        let policy = match self.duplicate_key_policy {
+            DuplicateKeyPolicy::Deserializer => DuplicateKeyPolicy::Deserializer,
+            _ => DuplicateKeyPolicy::Error,
+        };
+        self.check(policy, markdown, &extracted)?;
+
+        let metadata = deserialize::<T>(markdown, &extracted)?;
+
+        Ok(DocumentRef {
+            metadata: Some(metadata),
+            front_matter: extracted.front_matter,
+            content: extracted.body,
+            format: Some(extracted.format),
+            layout: Some(extracted.layout),
+        })
+    }
+
+    /// Splits the provided Markdown into its raw front matter and its body
+    /// without deserializing the front matter.
+    ///
+    /// Returns `None` if the document has no front matter.
+    pub fn split<'a>(&self, markdown: &'a str) -> Result<Option<Split<'a>>, FrontMatterError> {
+        let split = self.extract(markdown)?.map(|extracted| Split {
+            front_matter: extracted.front_matter,
+            body: extracted.body,
+            format: extracted.format,
+            layout: extracted.layout,
+        });
+
+        Ok(split)
+    }
+
+    /// Finds the keys defined more than once in the same mapping of the
+    /// YAML front matter of the provided Markdown, which serde doesn't
+    /// tell apart from invalid YAML.
+    ///
+    /// Documents without front matter, with front matter in another format
+    /// or with invalid YAML have no duplicate keys.
+    ///
+    /// ```
+    /// use yaml_front_matter::Parser;
+    ///
+    /// let markdown = "---\ntitle: 'First'\ntitle: 'Second'\n---\n";
+    /// let duplicates = Parser::new().duplicate_keys(markdown).unwrap();
+    ///
+    /// assert_eq!(duplicates[0].path(), "title");
+    /// assert_eq!(duplicates[0].first().line(), 2);
+    /// assert_eq!(duplicates[0].second().line(), 3);
+    /// ```
+    #[cfg(feature = "checks")]
+    pub fn duplicate_keys(&self, markdown: &str) -> Result<Vec<DuplicateKey>, FrontMatterError> {
+        let extracted = match self.extract(markdown)? {
+            Some(extracted) if extracted.format == Format::Yaml => extracted,
+            _ => return Ok(Vec::new()),
+        };
+        let index = match YamlIndex::new(extracted.front_matter) {
+            Some(index) => index,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(index
+            .duplicates
+            .iter()
+            .filter_map(|duplicate| locate_duplicate(markdown, &extracted, duplicate))
+            .collect())
+    }
+
+    /// Deserializes the extracted front matter into `T` once checked, along
+    /// with its warnings.
+    fn deserialize_checked<T: DeserializeOwned>(
+        &self,
+        markdown: &str,
+        extracted: &Extracted<'_>,
+    ) -> Result<(T, Vec<Warning>), FrontMatterError> {
+        let (front_matter, warnings) =
+            self.check(self.duplicate_key_policy, markdown, extracted)?;
+        let metadata = match front_matter {
+            Some(front_matter) => deserialize::<T>(
+                markdown,
+                &Extracted {
+                    front_matter: &front_matter,
+                    ..extracted.clone()
+                },
+            )?,
+            None => deserialize::<T>(markdown, extracted)?,
+        };
+
+        Ok((metadata, warnings))
+    }
+
+    /// Checks the extracted front matter, handling its duplicate keys
+    /// according to `policy`.
+    ///
+    /// Returns the front matter without the duplicate entries it drops, if
+    /// any, along with the warnings which are not promoted to errors.
+    fn check(
+        &self,
+        policy: DuplicateKeyPolicy,
+        markdown: &str,
+        extracted: &Extracted<'_>,
+    ) -> Result<(Option<String>, Vec<Warning>), FrontMatterError> {
+        let mut warnings = self.checks.check(markdown, extracted);
+        let (front_matter, yaml_warnings) = self.check_yaml(policy, markdown, extracted)?;
+
+        warnings.extend(yaml_warnings);
+
+        Ok((front_matter, self.checks.promote(warnings)?))
+    }
+
+    /// Checks the extracted YAML front matter, indexing it only when its
+    /// duplicate keys or its keys are checked.
+    #[cfg(feature = "checks")]
+    fn check_yaml(
+        &self,
+        policy: DuplicateKeyPolicy,
+        markdown: &str,
+        extracted: &Extracted<'_>,
+    ) -> Result<(Option<String>, Vec<Warning>), FrontMatterError> {
+        // Invalid YAML is reported when deserializing
+        let index = match extracted.format {
+            Format::Yaml
+                if policy != DuplicateKeyPolicy::Deserializer
+                    || self.checks.needs_index(extracted) =>
+            {
+                YamlIndex::new(extracted.front_matter)
+            }
+            _ => None,
+        };
+        let duplicates = index.as_ref().map_or(&[][..], |index| &index.duplicates);
+        let front_matter = drop_duplicate_keys(policy, markdown, extracted, duplicates)?;
+        let warned = match policy {
+            DuplicateKeyPolicy::Warn => duplicates
+                .iter()
+                .filter_map(|duplicate| locate_duplicate(markdown, extracted, duplicate))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let warnings = self
+            .checks
+            .check_yaml(markdown, extracted, index.as_ref(), warned);
+
+        Ok((front_matter, warnings))
+    }
+
+    /// Leaves duplicate keys to the deserializer, as the YAML front matter
+    /// is only indexed with the `checks` feature.
+    #[cfg(not(feature = "checks"))]
+    fn check_yaml(
+        &self,
+        _: DuplicateKeyPolicy,
+        _: &str,
+        _: &Extracted<'_>,
+    ) -> Result<(Option<String>, Vec<Warning>), FrontMatterError> {
+        Ok((None, Vec::new()))
+    }
+
+    /// Splits the Markdown into its front matter and its body.
+    ///
+    /// Returns `None` if the document has no front matter.
+    pub(crate) fn extract<'a>(
+        &self,
+        markdown: &'a str,
+    ) -> Result<Option<Extracted<'a>>, FrontMatterError> {
+        let mut opening: Option<(Format, &str)> = None;
+        let mut closed = false;
+        let mut line_number = 0;
+        let mut first_line = 0;
+        let mut offset = 0;
+        let mut span = 0..0;
+        let mut opening_fence = 0..0;
+        let mut closing_fence = 0..0;
+
+        for text in markdown.split_inclusive('\n') {
+            let line = trim_line_ending(text);
+
+            line_number += 1;
+            offset += text.len();
+
+            let fence = match opening {
+                Some((_, fence)) => fence,
+                None => {
+                    let line = if line_number == 1 && self.allow_bom {
+                        line.trim_start_matches(BOM)
+                    } else {
+                        line
+                    };
+
+                    let line_start = offset - text.len();
+                    let leading = markdown[..line_start]
+                        .trim_start_matches(BOM)
+                        .trim()
+                        .is_empty();
+
+                    // Only YAML front matter may follow other content, so a
+                    // `+++` line in the body doesn't open TOML front matter
+                    opening = self
+                        .opening_fence(line)
+                        .filter(|(format, _)| *format == Format::Yaml || leading);
+
+                    if opening.is_some() {
+                        first_line = line_number;
+                        span = offset..offset;
+                        // The fence ends with the line, past a byte order mark
+                        let end = offset - text.len() + trim_line_ending(text).len();
+
+                        opening_fence = end - line.len()..end;
+                        continue;
+                    }
+
+                    #[cfg(feature = "json")]
+                    {
+                        if leading && line.trim_start().starts_with('{') {
+                            let start = line_start + text.find('{').unwrap_or_default();
+
+                            // Liquid tags and Hugo shortcodes open with `{` as
+                            // well, only an object opens JSON front matter
+                            if matches!(
+                                markdown[start + 1..].trim_start().chars().next(),
+                                Some('"' | '}')
+                            ) {
+                                return self.extract_json(markdown, start, line_number);
+                            }
+                        }
+                    }
+
+                    if line.trim().is_empty() {
+                        if self.strict && !self.allow_leading_blank_lines {
+                            return Ok(None);
+                        }
+                    } else if self.strict {
+                        return Ok(None);
+                    }
+
+                    continue;
+                }
+            };
+
+            if line.trim() == fence {
+                let start = offset - text.len();
+
+                closed = true;
+                closing_fence = start..start + line.len();
+                break;
+            }
+
+            span.end = offset;
+        }
+
+        let format = match opening {
+            Some((format, _)) => format,
+            None => return Ok(None),
+        };
+
+        if !closed {
+            return self.unterminated(markdown, first_line);
+        }
+
+        let body = self.body(markdown, closing_fence.end);
+
+        Ok(Some(Extracted {
+            front_matter: &markdown[span.clone()],
+            body,
+            first_line,
+            first_column: 0,
+            layout: Layout::new(
+                markdown,
+                opening_fence,
+                span.clone(),
+                closing_fence,
+                markdown.len() - body.len()..markdown.len(),
+            ),
+            span,
+            format,
+        }))
+    }
+
+    /// Extracts a bare JSON object starting at the byte `start`, on the
+    /// 1-based `line` of the Markdown.
+    #[cfg(feature = "json")]
+    fn extract_json<'a>(
+        &self,
+        markdown: &'a str,
+        start: usize,
+        line: usize,
+    ) -> Result<Option<Extracted<'a>>, FrontMatterError> {
+        let line_start = markdown[..start].rfind('\n').map_or(0, |index| index + 1);
+        let first_column = markdown[line_start..start].chars().count();
+        let mut values = serde_json::Deserializer::from_str(&markdown[start..])
+            .into_iter::<serde::de::IgnoredAny>();
+
+        match values.next() {
+            Some(Ok(_)) => {}
+            Some(Err(err)) if err.is_eof() => return self.unterminated(markdown, line),
+            Some(Err(err)) => {
+                let location = Location::in_front_matter(
+                    markdown,
+                    (line - 1, first_column),
+                    err.line(),
+                    err.column(),
+                );
+                let err = BackendError::json(BackendErrorKind::Syntax, err);
+
+                return Err(FrontMatterError::Syntax {
+                    format: Format::Json,
+                    message: err.message,
+                    source: err.source,
+                    location,
+                });
+            }
+            None => return Ok(None),
+        }
+
+        let span = start..start + values.byte_offset();
+        // Trailing whitespace on the line of the closing brace belongs to the
+        // front matter, as it does for fences
+        let rest = markdown[span.end..].split_inclusive('\n').next();
+        let rest = trim_line_ending(rest.unwrap_or_default());
+        let body_start = match rest.trim().is_empty() {
+            true => span.end + rest.len(),
+            false => span.end,
+        };
+
+        let body = self.body(markdown, body_start);
+
+        Ok(Some(Extracted {
+            front_matter: &markdown[span.clone()],
+            body,
+            first_line: line - 1,
+            first_column,
+            layout: Layout::new(
+                markdown,
+                start..start,
+                span.clone(),
+                span.end..span.end,
+                markdown.len() - body.len()..markdown.len(),
+            ),
+            span,
+            format: Format::Json,
+        }))
+    }
+
+    /// Returns the body of the Markdown starting at the byte `start`, right
+    /// after the closing fence, stripping the line ending of the fence if
+    /// `strip_fence_newline` is enabled.
+    fn body<'a>(&self, markdown: &'a str, start: usize) -> &'a str {
+        let body = &markdown[start..];
+
+        if !self.strip_fence_newline {
+            return body;
+        }
+
+        body.strip_prefix("\r\n")
+            .or_else(|| body.strip_prefix('\n'))
+            .unwrap_or(body)
+    }
+
+    /// Handles a front matter opened on the 1-based `line` which is never
+    /// closed.
+    fn unterminated<'a>(
+        &self,
+        markdown: &str,
+        line: usize,
+    ) -> Result<Option<Extracted<'a>>, FrontMatterError> {
+        if self.allow_unterminated {
+            return Ok(None);
+        }
+
+        let fence = markdown.lines().nth(line - 1).unwrap_or_default();
+        let column = fence.chars().take_while(|c| c.is_whitespace()).count() + 1;
+
+        Err(FrontMatterError::MissingClosingFence {
+            line,
+            location: Location::new(markdown, line, column),
+        })
+    }
+
+    /// Returns the format of the front matter opened by `line` along with
+    /// its closing fence, if any.
+    fn opening_fence(&self, line: &str) -> Option<(Format, &'static str)> {
+        if self.strict {
+            Format::from_fence(line.trim_end())
+        } else {
+            Format::from_fence(line.trim())
+        }
+    }
+}
+
+/// Tells when a Markdown document read line by line has been read up to the
+/// end of its front matter.
+struct HeadScanner {
+    /// The parser reading the document, which must not allow unterminated
+    /// front matter as a front matter not closed yet is not an error until
+    /// the end of the document
+    parser: Parser,
+    /// The lines of the document read so far
+    head: String,
+    /// Whether a front matter was opened by the lines read so far
+    opened: bool,
+}
+
+impl HeadScanner {
+    fn new(parser: &Parser) -> HeadScanner {
+        HeadScanner {
+            parser: parser.clone().allow_unterminated(false),
+            head: String::new(),
+            opened: false,
+        }
+    }
+
+    /// Returns `true` if reading can stop after the line appended to the
+    /// head at the byte `start`, either because the front matter is closed
+    /// or because the document has none.
+    fn is_complete(&mut self, start: usize) -> Result<bool, FrontMatterError> {
+        let line = self.head[start..].trim_start_matches(BOM).trim();
+        // Extracting the front matter read so far is only worth it on lines
+        // which may open or close it
+        let may_end = match self.opened {
+            true => Format::from_fence(line).is_some() || line.contains('}'),
+            false if self.parser.strict => {
+                !line.is_empty() || !self.parser.allow_leading_blank_lines
+            }
+            false => Format::from_fence(line).is_some() || line.starts_with('{'),
+        };
+
+        if !may_end {
+            return Ok(false);
+        }
+
+        match self.parser.extract(&self.head) {
+            Ok(Some(_)) => Ok(true),
+            Ok(None) => Ok(self.parser.strict),
+            Err(FrontMatterError::MissingClosingFence { .. }) => {
+                self.opened = true;
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// The raw sections of a Markdown document split by `Parser::extract`
+#[derive(Clone)]
+pub(crate) struct Extracted<'a> {
+    /// The front matter between the opening and closing fences
+    pub(crate) front_matter: &'a str,
+    /// The Markdown following the closing fence, byte for byte
+    pub(crate) body: &'a str,
+    /// The 0-based index of the line where the front matter begins
+    pub(crate) first_line: usize,
+    /// The number of characters preceding the front matter on its first
+    /// line, which only bare JSON front matter may have
+    pub(crate) first_column: usize,
+    /// Where the fences, the front matter and the body live
+    pub(crate) layout: Layout,
+    /// The byte range of the front matter in the Markdown document
+    pub(crate) span: Range<usize>,
+    /// The format of the front matter, told apart by its fences
+    pub(crate) format: Format,
+}
+
+impl Extracted<'_> {
+    /// Maps a 1-based `line` and `column` relative to the front matter back
+    /// to the Markdown document.
+    pub(crate) fn locate(&self, markdown: &str, line: usize, column: usize) -> Option<Location> {
+        Location::in_front_matter(markdown, (self.first_line, self.first_column), line, column)
+    }
+}
+
+/// Strips the `\n` or `\r\n` line ending, as `str::lines` does.
+pub(crate) fn trim_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Reads the document at `path` and parses it with `parse`, attaching the
+/// path to any error.
+pub(crate) fn read_file<T>(
+    path: &Path,
+    parse: impl FnOnce(&str) -> Result<T, FrontMatterError>,
+) -> Result<T, FrontMatterError> {
+    fs::read_to_string(path)
+        .map_err(FrontMatterError::from)
+        .and_then(|markdown| parse(&markdown))
+        .map_err(|err| err.in_file(path.to_path_buf()))
+}
+
+/// Reads the whole document from `reader`, which must be valid UTF-8.
+pub(crate) fn read_to_string<R: BufRead>(mut reader: R) -> Result<String, FrontMatterError> {
+    let mut markdown = String::new();
+
+    reader.read_to_string(&mut markdown)?;
+
+    Ok(markdown)
+}
+
+/// Returns `true` if the YAML has nothing but blank lines and comments.
+fn is_blank(yaml: &str) -> bool {
+    yaml.lines().all(|line| {
+        let line = line.trim();
+
+        line.is_empty() || line.starts_with('#')
+    })
+}
+
+/// Handles the `duplicates` keys of the extracted YAML front matter
+/// according to `policy`, returning the front matter with the entries
+/// it drops blanked out, if any.
+///
+/// Blanking keeps the lines and columns of the remaining entries, so error
+/// locations still point at the Markdown document.
+#[cfg(feature = "checks")]
+fn drop_duplicate_keys(
+    policy: DuplicateKeyPolicy,
+    markdown: &str,
+    extracted: &Extracted<'_>,
+    duplicates: &[DuplicateEntry],
+) -> Result<Option<String>, FrontMatterError> {
+    if duplicates.is_empty() {
+        return Ok(None);
+    }
+
+    let dropped = match policy {
+        DuplicateKeyPolicy::Deserializer => return Ok(None),
+        DuplicateKeyPolicy::Error => {
+            return match duplicates
+                .iter()
+                .find_map(|duplicate| locate_duplicate(markdown, extracted, duplicate))
+            {
+                Some(duplicate) => Err(FrontMatterError::DuplicateKey(Box::new(duplicate))),
+                None => Ok(None),
+            };
+        }
+        DuplicateKeyPolicy::KeepFirst => duplicates
+            .iter()
+            .map(|duplicate| {
+                (
+                    duplicate,
+                    &duplicate.second_entry,
+                    duplicate.second_anchored,
+                )
+            })
+            .collect::<Vec<_>>(),
+        // Every entry of the key but the last one
+        DuplicateKeyPolicy::Warn | DuplicateKeyPolicy::KeepLast => duplicates
+            .iter()
+            .map(|duplicate| (duplicate, &duplicate.first_entry, duplicate.first_anchored))
+            .chain(
+                duplicates
+                    .iter()
+                    .filter(|duplicate| {
+                        duplicates.iter().any(|other| {
+                            other.first_entry == duplicate.first_entry
+                                && other.second_entry.start > duplicate.second_entry.start
+                        })
+                    })
+                    .map(|duplicate| {
+                        (
+                            duplicate,
+                            &duplicate.second_entry,
+                            duplicate.second_anchored,
+                        )
+                    }),
+            )
+            .collect::<Vec<_>>(),
+    };
+
+    // Aliases elsewhere may refer to an anchor of a dropped entry
+    if let Some(duplicate) = dropped
+        .iter()
+        .filter(|(_, _, anchored)| *anchored)
+        .find_map(|(duplicate, _, _)| locate_duplicate(markdown, extracted, duplicate))
+    {
+        return Err(FrontMatterError::DuplicateKey(Box::new(duplicate)));
+    }
+
+    let front_matter = extracted
+        .front_matter
+        .char_indices()
+        .map(|(offset, c)| {
+            let is_dropped = dropped.iter().any(|(_, range, _)| range.contains(&offset));
+
+            match c {
+                '\r' | '\n' => c,
+                _ if is_dropped => ' ',
+                _ => c,
+            }
+        })
+        .collect();
+
+    Ok(Some(front_matter))
+}
+
+/// Maps a key defined more than once in the extracted front matter back to
+/// the Markdown document.
+#[cfg(feature = "checks")]
+fn locate_duplicate(
+    markdown: &str,
+    extracted: &Extracted<'_>,
+    duplicate: &DuplicateEntry,
+) -> Option<DuplicateKey> {
+    let locate = |(line, column)| extracted.locate(markdown, line, column);
+
+    Some(DuplicateKey::new(
+        dotted(&duplicate.path),
+        locate(duplicate.first)?,
+        locate(duplicate.second)?,
+    ))
+}
+
+/// Deserializes the extracted front matter into `T`, telling apart invalid
+/// syntax from valid front matter which doesn't match the structure of `T`.
+///
+/// Error locations are mapped back to the Markdown document.
+pub(crate) fn deserialize<'a, T: Deserialize<'a>>(
+    markdown: &str,
+    extracted: &Extracted<'a>,
+) -> Result<T, FrontMatterError> {
+    let format = extracted.format;
+
+    format
+        .deserialize::<T>(extracted.front_matter)
+        .map_err(|err| {
+            let location = err
+                .position
+                .and_then(|(line, column)| extracted.locate(markdown, line, column));
+
+            match err.kind {
+                BackendErrorKind::Syntax => FrontMatterError::Syntax {
+                    format,
+                    message: err.message,
+                    source: err.source,
+                    location,
+                },
+                BackendErrorKind::Deserialize => FrontMatterError::Deserialize {
+                    format,
+                    message: err.message,
+                    source: err.source,
+                    location,
+                },
+            }
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, BufReader, Read};
+
+    use serde::Deserialize;
+
+    use super::Parser;
+    #[cfg(feature = "checks")]
+    use crate::DuplicateKeyPolicy;
+    use crate::FrontMatterError;
+
+    const THEMATIC_BREAK: &str = "# Title\n\nSome text\n\n---\n\nMore text\n\n---\n";
+
+    #[derive(Debug, Deserialize)]
+    struct Metadata {
+        title: String,
+    }
+
+    #[test]
+    fn strict_ignores_thematic_breaks() {
+        let document = Parser::new()
+            .strict(true)
+            .parse::<Metadata>(THEMATIC_BREAK)
+            .unwrap();
+
+        assert!(document.metadata.is_none());
+        assert_eq!(document.content, THEMATIC_BREAK);
+    }
+
+    #[test]
+    fn strict_parses_front_matter_on_first_line() {
+        let document = Parser::new()
+            .strict(true)
+            .parse::<Metadata>("---\ntitle: 'Strict'\n---\nBody")
+            .unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "Strict");
+        assert_eq!(document.content, "\nBody");
+    }
+
+    #[test]
+    fn preserves_body_bytes() {
+        let markdown = "---\r\ntitle: 'CRLF'\r\n---  \r\n\r\n# Title\r\n\r\n";
+        let exact = Parser::new().parse::<Metadata>(markdown).unwrap();
+        let stripped = Parser::new()
+            .strip_fence_newline(true)
+            .parse::<Metadata>(markdown)
+            .unwrap();
+
+        assert_eq!(exact.content, "\r\n\r\n# Title\r\n\r\n");
+        assert_eq!(stripped.content, "\r\n# Title\r\n\r\n");
+    }
+
+    #[test]
+    fn reads_head_up_to_closing_fence() {
+        struct Unreadable;
+
+        impl Read for Unreadable {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("body was read"))
+            }
+        }
+
+        let markdown = "---\ntitle: 'Head'\n---\n".as_bytes();
+        let mut reader = BufReader::new(markdown.chain(Unreadable));
+        let head = Parser::new().read_head::<Metadata, _>(&mut reader).unwrap();
+
+        assert_eq!(head.metadata.unwrap().title, "Head");
+        assert_eq!(head.body_offset, 21);
+        assert!(reader.read(&mut [0]).is_err());
+    }
+
+    #[test]
+    fn reads_head_of_documents_without_front_matter() {
+        let strict = Parser::new()
+            .strict(true)
+            .read_head::<Metadata, _>(BufReader::new(THEMATIC_BREAK.as_bytes()))
+            .unwrap();
+        let unterminated = Parser::new()
+            .allow_unterminated(true)
+            .read_head::<Metadata, _>("---\ntitle: 'Head'\n".as_bytes())
+            .unwrap();
+
+        assert!(strict.metadata.is_none());
+        assert!(unterminated.metadata.is_none());
+        assert!(matches!(
+            Parser::new().read_head::<Metadata, _>("---\ntitle: 'Head'\n".as_bytes()),
+            Err(FrontMatterError::MissingClosingFence { line: 1, .. })
+        ));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn reads_head_asynchronously() {
+        let markdown = "---\ntitle: 'Head'\n---\n# Body\n".as_bytes();
+        let mut reader = tokio::io::BufReader::new(markdown);
+        let head = Parser::new()
+            .read_head_async::<Metadata, _>(&mut reader)
+            .await
+            .unwrap();
+        let mut body = String::new();
+
+        tokio::io::AsyncReadExt::read_to_string(&mut reader, &mut body)
+            .await
+            .unwrap();
+
+        assert_eq!(head.metadata.unwrap().title, "Head");
+        assert_eq!(body, "# Body\n");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn parses_asynchronously() {
+        let document = Parser::new()
+            .parse_async::<Metadata, _>("---\ntitle: 'Async'\n---\n".as_bytes())
+            .await
+            .unwrap();
+        let err = Parser::new()
+            .parse_file_async::<Metadata, _>("missing/post.md")
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "Async");
+        assert!(matches!(err, FrontMatterError::File { .. }));
+    }
+
+    #[test]
+    fn strict_skips_bom() {
+        let markdown = "\u{feff}---\ntitle: 'BOM'\n---\n";
+
+        assert!(Parser::new()
+            .strict(true)
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_some());
+        assert!(Parser::new()
+            .strict(true)
+            .allow_bom(false)
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_none());
+    }
+
+    #[test]
+    fn strict_skips_leading_blank_lines_when_allowed() {
+        let markdown = "\n\n---\ntitle: 'Blank'\n---\n";
+
+        assert!(Parser::new()
+            .strict(true)
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_none());
+        assert!(Parser::new()
+            .strict(true)
+            .allow_leading_blank_lines(true)
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_some());
+    }
+
+    #[test]
+    fn parse_ref_borrows_from_markdown() {
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            title: &'a str,
+        }
+
+        let markdown = String::from("---\ntitle: Borrowed\n---\n# Title\n");
+        let document = Parser::new().parse_ref::<Borrowed>(&markdown).unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "Borrowed");
+        assert_eq!(document.front_matter, "title: Borrowed\n");
+        assert_eq!(document.content, "\n# Title\n");
+    }
+
+    #[test]
+    fn optional_tells_apart_absent_empty_and_present() {
+        let parser = Parser::new().strict(true);
+        let absent = parser.parse_optional::<Metadata>("# Title\n").unwrap();
+        let empty = parser
+            .parse_optional::<Metadata>("---\n# Nothing yet\n\n---\n# Title")
+            .unwrap();
+        let present = parser
+            .parse_optional::<Metadata>("---\ntitle: 'Present'\n---\n# Title")
+            .unwrap();
+
+        assert!(absent.metadata.is_absent());
+        assert_eq!(absent.content, "# Title\n");
+        assert!(empty.metadata.is_empty());
+        assert_eq!(empty.content, "\n# Title");
+        assert_eq!(present.metadata.into_option().unwrap().title, "Present");
+    }
+
+    const DUPLICATES: &str =
+        "---\ntitle: 'First'\nauthor:\n  name: 'A'\n  name: 'B'\ntitle: 'Second'\ntitle: 'Third'\n---\n";
+
+    #[cfg(feature = "checks")]
+    #[test]
+    fn reports_duplicate_key_with_both_locations() {
+        let err = Parser::new()
+            .duplicate_key_policy(DuplicateKeyPolicy::Error)
+            .parse::<serde_yaml::Value>(DUPLICATES)
+            .err()
+            .unwrap();
+
+        match err {
+            FrontMatterError::DuplicateKey(duplicate) => {
+                assert_eq!(duplicate.path(), "author.name");
+                assert_eq!(duplicate.first().line(), 4);
+                assert_eq!(duplicate.second().line(), 5);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        assert!(matches!(
+            Parser::new()
+                .duplicate_key_policy(DuplicateKeyPolicy::KeepLast)
+                .parse_ref::<serde_yaml::Value>(DUPLICATES),
+            Err(FrontMatterError::DuplicateKey(_))
+        ));
+    }
+
+    #[test]
+    fn leaves_duplicate_keys_to_deserializer_by_default() {
+        let err = Parser::new()
+            .parse::<serde_yaml::Value>(DUPLICATES)
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, FrontMatterError::Syntax { .. }));
+    }
+
+    #[cfg(feature = "checks")]
+    #[test]
+    fn keeps_first_or_last_duplicate_value() {
+        let parse = |policy| {
+            Parser::new()
+                .duplicate_key_policy(policy)
+                .parse::<serde_yaml::Value>(DUPLICATES)
+                .unwrap()
+                .metadata
+                .unwrap()
+        };
+        let first = parse(DuplicateKeyPolicy::KeepFirst);
+        let last = parse(DuplicateKeyPolicy::KeepLast);
+
+        assert_eq!(first["title"], "First");
+        assert_eq!(first["author"]["name"], "A");
+        assert_eq!(last["title"], "Third");
+        assert_eq!(last["author"]["name"], "B");
+        assert_eq!(parse(DuplicateKeyPolicy::Warn), last);
+    }
+
+    #[cfg(feature = "checks")]
+    #[test]
+    fn refuses_to_drop_anchored_duplicates() {
+        let markdown = "---\na: &x 1\na: 2\nb: *x\n---\n";
+        let err = Parser::new()
+            .duplicate_key_policy(DuplicateKeyPolicy::KeepLast)
+            .parse::<serde_yaml::Value>(markdown)
+            .err()
+            .unwrap();
+
+        match err {
+            FrontMatterError::DuplicateKey(duplicate) => {
+                assert_eq!(duplicate.first().line(), 2);
+                assert_eq!(duplicate.second().line(), 3);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        let document = Parser::new()
+            .duplicate_key_policy(DuplicateKeyPolicy::KeepFirst)
+            .parse::<serde_yaml::Value>(markdown)
+            .unwrap();
+
+        assert_eq!(document.metadata.unwrap()["b"], 1);
+    }
+
+    #[test]
+    fn tells_keys_apart_by_resolved_value() {
+        let markdown = "---\n1: a\n'1': b\n---\n";
+        let metadata = Parser::new()
+            .parse::<serde_yaml::Value>(markdown)
+            .unwrap()
+            .metadata
+            .unwrap();
+
+        assert_eq!(metadata[1], "a");
+        assert_eq!(metadata["1"], "b");
+    }
+
+    #[cfg(feature = "checks")]
+    #[test]
+    fn locates_errors_past_dropped_duplicates() {
+        let markdown = "---\ntitle: 'First'\n\ntitle: [1, 2]\n---\n";
+        let err = Parser::new()
+            .duplicate_key_policy(DuplicateKeyPolicy::KeepLast)
+            .parse::<Metadata>(markdown)
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, FrontMatterError::Deserialize { .. }));
+        assert_eq!(err.location().unwrap().line(), 4);
+    }
+
+    #[test]
+    fn reports_line_of_unterminated_fence() {
+        let markdown = "\n---\ntitle: 'Unterminated'\n\n# Title\n";
+        let err = Parser::new().parse::<Metadata>(markdown).err().unwrap();
+
+        match err {
+            FrontMatterError::MissingClosingFence { line, location } => {
+                assert_eq!(line, 2);
+                assert_eq!(&markdown[location.unwrap().span()], "---");
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn lenient_ignores_unterminated_fence() {
+        let markdown = "---\ntitle: 'Unterminated'\n\n# Title\n";
+        let document = Parser::new()
+            .allow_unterminated(true)
+            .parse::<Metadata>(markdown)
+            .unwrap();
+
+        assert!(document.metadata.is_none());
+        assert_eq!(document.content, markdown);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn parses_toml_front_matter() {
+        let markdown = "+++\ntitle = 'TOML'\n+++\n# Title";
+        let document = Parser::new().parse::<Metadata>(markdown).unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "TOML");
+        assert_eq!(document.content, "\n# Title");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn ignores_toml_fence_after_content() {
+        let markdown = "# Title\n\n+++\n\n---\ntitle: 'YAML'\n---\n";
+        let document = Parser::new().parse::<Metadata>(markdown).unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "YAML");
+        assert_eq!(document.format, Some(crate::Format::Yaml));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn maps_toml_error_location_to_markdown() {
+        let markdown = "\n+++\ntitle = 'TOML'\nslug = \n+++\n";
+        let err = Parser::new().parse::<Metadata>(markdown).err().unwrap();
+
+        assert!(matches!(
+            err,
+            FrontMatterError::Syntax {
+                format: crate::Format::Toml,
+                ..
+            }
+        ));
+        assert_eq!(err.location().unwrap().line(), 4);
+    }
+
+    #[cfg(not(feature = "toml"))]
+    #[test]
+    fn ignores_toml_front_matter_without_feature() {
+        let markdown = "+++\ntitle = 'TOML'\n+++\n# Title";
+
+        assert!(Parser::new()
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_none());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parses_bare_json_front_matter() {
+        let markdown = "{\n  \"title\": \"JSON\"\n}\n# Title";
+        let document = Parser::new().parse::<Metadata>(markdown).unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "JSON");
+        assert_eq!(document.content, "\n# Title");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parses_fenced_json_front_matter() {
+        let markdown = ";;;\n{ \"title\": \"JSON\" }\n;;;\n# Title";
+        let document = Parser::new().parse::<Metadata>(markdown).unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "JSON");
+        assert_eq!(document.content, "\n# Title");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn locates_errors_on_indented_bare_json() {
+        let err = Parser::new()
+            .parse::<Metadata>("  {\"title\": 1}\n# Title")
+            .err()
+            .unwrap();
+        let location = err.location().unwrap();
+
+        assert!(matches!(err, FrontMatterError::Deserialize { .. }));
+        assert_eq!((location.line(), location.column()), (1, 13));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn reports_malformed_bare_json() {
+        let unterminated = "\n{\n  \"title\": \"JSON\",\n";
+        let malformed = "\n{\n  \"title\": \"JSON\"\n# Title";
+
+        assert!(matches!(
+            Parser::new().parse::<Metadata>(unterminated),
+            Err(FrontMatterError::MissingClosingFence { line: 2, .. })
+        ));
+        assert_eq!(
+            Parser::new()
+                .parse::<Metadata>(malformed)
+                .err()
+                .unwrap()
+                .location()
+                .unwrap()
+                .line(),
+            4
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn ignores_liquid_tags_and_shortcodes() {
+        let liquid = "{% include header.html %}\n# Hello\n";
+        let shortcode = "{{< figure src=\"a.png\" >}}\n# Hello\n";
+
+        for markdown in [liquid, shortcode] {
+            let document = Parser::new().parse::<Metadata>(markdown).unwrap();
+
+            assert!(document.metadata.is_none());
+            assert_eq!(document.content, markdown);
+            assert!(Parser::new()
+                .strict(true)
+                .parse::<Metadata>(markdown)
+                .unwrap()
+                .metadata
+                .is_none());
+        }
+
+        let document = Parser::new()
+            .parse::<Metadata>("{% raw %}\n---\ntitle: 'Liquid'\n---\n")
+            .unwrap();
+
+        assert_eq!(document.metadata.unwrap().title, "Liquid");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn ignores_json_after_content() {
+        let markdown = "# Title\n{ \"title\": \"JSON\" }\n";
+
+        assert!(Parser::new()
+            .parse::<Metadata>(markdown)
+            .unwrap()
+            .metadata
+            .is_none());
+    }
+}
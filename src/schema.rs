@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use crate::index::YamlIndex;
+use crate::parser::{deserialize, Extracted};
+use crate::{Format, FrontMatterError, Location, Violation};
+use jsonschema::JSONSchema;
+use serde::Serialize;
+
+/// A JSON Schema to validate front matter against, for constraints serde
+/// can't check such as the number of items of a list or the pattern of a
+/// string.
+///
+/// Every violation of the schema is reported, with its location in the
+/// Markdown document for YAML front matter.
+///
+/// ```
+/// use serde_json::json;
+/// use yaml_front_matter::{FrontMatterError, Parser, Schema};
+///
+/// let schema = Schema::new(&json!({
+///     "type": "object",
+///     "properties": {
+///         "slug": { "type": "string", "pattern": "^[a-z0-9-]+$" },
+///         "tags": { "type": "array", "minItems": 1, "maxItems": 5 }
+///     }
+/// }))
+/// .unwrap();
+///
+/// let markdown = "---\nslug: Hello World\ntags: []\n---\n";
+/// let err = Parser::new().validate(markdown, &schema).unwrap_err();
+///
+/// match err {
+///     FrontMatterError::Validation(violations) => {
+///         assert_eq!(violations.len(), 2);
+///         assert_eq!(violations[0].path(), "/slug");
+///         assert_eq!(violations[0].location().unwrap().line(), 2);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Schema {
+    compiled: JSONSchema,
+}
+
+impl Schema {
+    /// Compiles the JSON Schema `schema`.
+    pub fn new(schema: &serde_json::Value) -> Result<Schema, FrontMatterError> {
+        let compiled = JSONSchema::compile(schema)
+            .map_err(|err| FrontMatterError::InvalidSchema(err.to_string().into()))?;
+
+        Ok(Schema { compiled })
+    }
+
+    /// Validates the `metadata` of a document, once deserialized, against
+    /// the schema.
+    ///
+    /// Violations have no location as the metadata is no longer tied to the
+    /// Markdown document, use `Parser::validate` to validate front matter
+    /// before deserializing it instead.
+    pub fn validate<T: Serialize>(&self, metadata: &T) -> Result<(), FrontMatterError> {
+        let value = serde_json::to_value(metadata)
+            .map_err(|err| FrontMatterError::Serialize(Box::new(err)))?;
+
+        self.check(&value, |_| None)
+    }
+
+    /// Validates the front matter extracted from `markdown` against the
+    /// schema.
+    pub(crate) fn validate_front_matter(
+        &self,
+        markdown: &str,
+        extracted: &Extracted<'_>,
+    ) -> Result<(), FrontMatterError> {
+        let value = deserialize::<serde_json::Value>(markdown, extracted)?;
+        let positions = match extracted.format {
+            Format::Yaml => YamlIndex::new(extracted.front_matter)
+                .map(|index| index.positions)
+                .unwrap_or_default(),
+            _ => HashMap::new(),
+        };
+
+        self.check(&value, |path| {
+            let (line, column) = positions.get(path)?;
+
+            extracted.locate(markdown, *line, *column)
+        })
+    }
+
+    /// Collects every violation of the schema by `value`, locating them with
+    /// `locate` from their JSON pointer.
+    fn check(
+        &self,
+        value: &serde_json::Value,
+        locate: impl Fn(&str) -> Option<Location>,
+    ) -> Result<(), FrontMatterError> {
+        let errors = match self.compiled.validate(value) {
+            Ok(()) => return Ok(()),
+            Err(errors) => errors,
+        };
+        let violations = errors
+            .map(|err| {
+                let path = err.instance_path.to_string();
+
+                Violation::new(path.clone(), err.to_string(), locate(&path))
+            })
+            .collect();
+
+        Err(FrontMatterError::Validation(violations))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::Schema;
+    use crate::{FrontMatterError, Parser};
+
+    const MARKDOWN: &str = r#"---
+title: "Schema"
+slug: Not A Slug
+tags:
+  - rust
+  - 1
+---
+# Schema
+"#;
+
+    fn schema() -> Schema {
+        Schema::new(&json!({
+            "type": "object",
+            "required": ["title", "date"],
+            "properties": {
+                "slug": { "type": "string", "pattern": "^[a-z0-9-]+$" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_every_violation_with_location() {
+        let err = Parser::new().validate(MARKDOWN, &schema()).unwrap_err();
+        let violations = match err {
+            FrontMatterError::Validation(violations) => violations,
+            err => panic!("unexpected error: {}", err),
+        };
+        let mut located = violations
+            .iter()
+            .map(|violation| {
+                let location = violation.location().unwrap();
+
+                (violation.path(), location.line(), location.column())
+            })
+            .collect::<Vec<_>>();
+
+        located.sort();
+
+        assert_eq!(
+            located,
+            vec![("", 2, 1), ("/slug", 3, 7), ("/tags/1", 6, 5)]
+        );
+    }
+
+    #[test]
+    fn validates_deserialized_metadata() {
+        #[derive(Serialize)]
+        struct Metadata {
+            title: String,
+        }
+
+        let metadata = Metadata {
+            title: "Schema".to_string(),
+        };
+
+        assert!(matches!(
+            schema().validate(&metadata),
+            Err(FrontMatterError::Validation(violations)) if violations[0].location().is_none()
+        ));
+    }
+
+    #[test]
+    fn reports_metadata_failing_to_serialize() {
+        let metadata = std::collections::BTreeMap::from([(vec![1], "not a string key")]);
+
+        assert!(matches!(
+            schema().validate(&metadata),
+            Err(FrontMatterError::Serialize(_))
+        ));
+    }
+
+    #[test]
+    fn fails_on_invalid_schema() {
+        assert!(matches!(
+            Schema::new(&json!({ "type": 1 })),
+            Err(FrontMatterError::InvalidSchema(_))
+        ));
+    }
+}
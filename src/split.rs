@@ -0,0 +1,18 @@
+use crate::{Format, Layout};
+
+/// The raw front matter and body of a Markdown document, split by the same
+/// fence detection rules as `parse` without deserializing the front matter.
+///
+/// Useful to hash or display the front matter as written, or to hand it to
+/// another YAML library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Split<'a> {
+    /// The raw front matter between the fences
+    pub front_matter: &'a str,
+    /// The body of the Markdown following the closing fence, byte for byte
+    pub body: &'a str,
+    /// The format of the front matter, told apart by its fences
+    pub format: Format,
+    /// Where the front matter and the body live in the Markdown
+    pub layout: Layout,
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use crate::Location;
+
+/// A value of the front matter which violates a JSON Schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    path: String,
+    message: String,
+    location: Option<Location>,
+}
+
+impl Violation {
+    #[cfg(feature = "schema")]
+    pub(crate) fn new(path: String, message: String, location: Option<Location>) -> Violation {
+        Violation {
+            path,
+            message,
+            location,
+        }
+    }
+
+    /// The JSON pointer to the offending value, such as `/tags/0`, empty for
+    /// the whole front matter.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Describes the constraint of the schema being violated.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Where the offending value is in the Markdown document, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message)?,
+            false => write!(f, "{}: {}", self.path, self.message)?,
+        }
+
+        match &self.location {
+            Some(location) => write!(f, " at {}", location),
+            None => Ok(()),
+        }
+    }
+}
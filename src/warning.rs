@@ -0,0 +1,324 @@
+use std::fmt;
+
+#[cfg(feature = "checks")]
+use crate::index::{dotted, YamlIndex};
+use crate::parser::Extracted;
+#[cfg(feature = "checks")]
+use crate::{DuplicateKey, Format};
+use crate::{FrontMatterError, Location};
+
+/// A non-fatal issue with the front matter of a Markdown document, reported
+/// by `Parser::parse_with_warnings` alongside the parsed document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    code: WarningCode,
+    message: String,
+    location: Option<Location>,
+}
+
+/// The kind of a `Warning`, which `Parser::promote_warning` turns into an
+/// error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WarningCode {
+    /// A top-level key of a YAML front matter missing from
+    /// `Parser::known_keys`.
+    UnknownKey,
+    /// A key of a YAML front matter marked with `Parser::deprecated_key`.
+    DeprecatedKey,
+    /// Whitespace following the opening or closing fence on its line.
+    FenceWhitespace,
+    /// A line of YAML front matter indented with tabs, which YAML forbids,
+    /// checked with the `checks` feature.
+    TabIndentation,
+    /// A key defined more than once in the same mapping of a YAML front
+    /// matter under `DuplicateKeyPolicy::Warn`.
+    DuplicateKey,
+}
+
+/// The checks a `Parser` runs on front matter, along with the warning codes
+/// promoted to errors
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Checks {
+    /// The top-level keys expected in the front matter, any key if `None`
+    #[cfg(feature = "checks")]
+    pub(crate) known_keys: Option<Vec<String>>,
+    /// The dotted paths of deprecated keys along with what to do instead
+    #[cfg(feature = "checks")]
+    pub(crate) deprecated_keys: Vec<(String, String)>,
+    pub(crate) promoted: Vec<WarningCode>,
+}
+
+impl Warning {
+    pub(crate) fn new(code: WarningCode, message: String, location: Option<Location>) -> Warning {
+        Warning {
+            code,
+            message,
+            location,
+        }
+    }
+
+    /// The kind of the warning.
+    pub fn code(&self) -> WarningCode {
+        self.code
+    }
+
+    /// Describes the issue.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Where the issue is in the Markdown document, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        match &self.location {
+            Some(location) => write!(f, " at {}", location),
+            None => Ok(()),
+        }
+    }
+}
+
+impl WarningCode {
+    /// The name of the code, such as `unknown-key`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarningCode::UnknownKey => "unknown-key",
+            WarningCode::DeprecatedKey => "deprecated-key",
+            WarningCode::FenceWhitespace => "fence-whitespace",
+            WarningCode::TabIndentation => "tab-indentation",
+            WarningCode::DuplicateKey => "duplicate-key",
+        }
+    }
+}
+
+impl fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Checks {
+    /// Whether the checks need the extracted YAML front matter indexed,
+    /// for its keys or to tell apart tabs in block scalars.
+    #[cfg(feature = "checks")]
+    pub(crate) fn needs_index(&self, extracted: &Extracted<'_>) -> bool {
+        self.known_keys.is_some()
+            || !self.deprecated_keys.is_empty()
+            || extracted
+                .front_matter
+                .split_inclusive('\n')
+                .any(|text| indentation_tab(text).is_some())
+    }
+
+    /// Checks the fences of the front matter extracted from `markdown`.
+    pub(crate) fn check(&self, markdown: &str, extracted: &Extracted<'_>) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        let fences = [
+            ("opening", extracted.layout.opening_fence()),
+            ("closing", extracted.layout.closing_fence()),
+        ];
+
+        for (name, fence) in fences {
+            let line = &markdown[fence.bytes()];
+            let trimmed = line.trim_end();
+
+            if trimmed.len() < line.len() {
+                warnings.push(Warning::new(
+                    WarningCode::FenceWhitespace,
+                    format!("trailing whitespace after the {} fence", name),
+                    Location::at(markdown, fence.bytes().start + trimmed.len()),
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// Checks the indentation and the keys of the front matter extracted from
+    /// `markdown`, whose nodes are found in `index` for valid YAML front
+    /// matter, reporting the `duplicates` kept by the parser as well.
+    #[cfg(feature = "checks")]
+    pub(crate) fn check_yaml(
+        &self,
+        markdown: &str,
+        extracted: &Extracted<'_>,
+        index: Option<&YamlIndex>,
+        duplicates: Vec<DuplicateKey>,
+    ) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        if extracted.format == Format::Yaml {
+            let mut offset = extracted.span.start;
+            let block_scalars = index.map_or(&[][..], |index| &index.block_scalars);
+
+            for (line, text) in extracted.front_matter.split_inclusive('\n').enumerate() {
+                // Tabs are content within block scalars
+                let in_block_scalar = block_scalars
+                    .iter()
+                    .any(|lines| lines.contains(&(line + 1)));
+
+                if let Some(tab) = indentation_tab(text).filter(|_| !in_block_scalar) {
+                    warnings.push(Warning::new(
+                        WarningCode::TabIndentation,
+                        "line indented with a tab".to_string(),
+                        Location::at(markdown, offset + tab),
+                    ));
+                }
+
+                offset += text.len();
+            }
+        }
+
+        for (pointer, (line, column)) in index.map(|index| &index.keys).into_iter().flatten() {
+            let key = dotted(pointer);
+            let location = || extracted.locate(markdown, *line, *column);
+            let is_known = match &self.known_keys {
+                // Only top-level keys are checked
+                Some(known_keys) if pointer.matches('/').count() == 1 => known_keys.contains(&key),
+                _ => true,
+            };
+
+            if !is_known {
+                warnings.push(Warning::new(
+                    WarningCode::UnknownKey,
+                    format!("unknown key `{}`", key),
+                    location(),
+                ));
+            }
+
+            if let Some((_, note)) = self
+                .deprecated_keys
+                .iter()
+                .find(|(deprecated, _)| *deprecated == key)
+            {
+                warnings.push(Warning::new(
+                    WarningCode::DeprecatedKey,
+                    format!("deprecated key `{}`: {}", key, note),
+                    location(),
+                ));
+            }
+        }
+
+        warnings.extend(duplicates.into_iter().map(|duplicate| {
+            Warning::new(
+                WarningCode::DuplicateKey,
+                format!(
+                    "duplicate key `{}`, first defined at {}",
+                    duplicate.path(),
+                    duplicate.first()
+                ),
+                Some(duplicate.second().clone()),
+            )
+        }));
+
+        warnings
+    }
+
+    /// Sorts the `warnings` by their location, failing with the first one
+    /// whose code is promoted to an error.
+    pub(crate) fn promote(
+        &self,
+        mut warnings: Vec<Warning>,
+    ) -> Result<Vec<Warning>, FrontMatterError> {
+        warnings.sort_by_key(|warning| {
+            warning
+                .location
+                .as_ref()
+                .map(|location| (location.line(), location.column()))
+        });
+
+        match warnings
+            .iter()
+            .position(|warning| self.promoted.contains(&warning.code))
+        {
+            Some(promoted) => Err(FrontMatterError::Warning(Box::new(
+                warnings.swap_remove(promoted),
+            ))),
+            None => Ok(warnings),
+        }
+    }
+}
+
+/// The byte offset of the first tab indenting the `text` of a line, if it
+/// has any content.
+#[cfg(feature = "checks")]
+fn indentation_tab(text: &str) -> Option<usize> {
+    let indentation = &text[..text.len() - text.trim_start().len()];
+
+    indentation.find('\t').filter(|_| !text.trim().is_empty())
+}
+
+#[cfg(all(test, feature = "checks"))]
+mod test {
+    use super::WarningCode;
+    use crate::{DuplicateKeyPolicy, FrontMatterError, Parser};
+
+    const MARKDOWN: &str = "---  \ntitle: 'Warnings'\ncategories: [rust]\ntags: [\n\trust\n]\ntitle: 'Again'\n---\n# Warnings\n";
+
+    fn parser() -> Parser {
+        Parser::new()
+            .known_keys(["title", "tags", "author"])
+            .deprecated_key("categories", "use `tags` instead")
+            .duplicate_key_policy(DuplicateKeyPolicy::Warn)
+    }
+
+    #[test]
+    fn reports_warnings_with_locations() {
+        let warnings = parser()
+            .parse_with_warnings::<serde_yaml::Value>(MARKDOWN)
+            .unwrap()
+            .warnings
+            .into_iter()
+            .map(|warning| {
+                let location = warning.location().unwrap();
+
+                (warning.code(), location.line(), location.column())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            warnings,
+            vec![
+                (WarningCode::FenceWhitespace, 1, 4),
+                (WarningCode::UnknownKey, 3, 1),
+                (WarningCode::DeprecatedKey, 3, 1),
+                (WarningCode::TabIndentation, 5, 1),
+                (WarningCode::DuplicateKey, 7, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_tabs_in_block_scalars() {
+        let markdown = "---\nd: |\n  a\n  \tb\ne: 1\n---\n";
+        let warnings = Parser::new()
+            .promote_warning(WarningCode::TabIndentation)
+            .parse_with_warnings::<serde_yaml::Value>(markdown)
+            .unwrap()
+            .warnings;
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn promotes_warnings_to_errors() {
+        let err = parser()
+            .promote_warning(WarningCode::DeprecatedKey)
+            .parse::<serde_yaml::Value>(MARKDOWN)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "deprecated key `categories`: use `tags` instead at line 3, column 1"
+        );
+        assert!(matches!(err, FrontMatterError::Warning(_)));
+    }
+}